@@ -62,7 +62,7 @@ fn impl_user_define_bit_width(st: &mut syn::ItemStruct)->syn::Result<proc_macro2
         })
     }
     let st_vis = st.vis.clone();
-    let st_token = st.struct_token.clone();
+    let st_token = st.struct_token;
     let st_ident = st.ident.clone();
     let st_size = struct_visit.size / 8;
 
@@ -255,14 +255,13 @@ impl syn::visit_mut::VisitMut for StructVisitor{
                 }
             }
             let ty_size:u8 = ty.chars().skip(1).collect::<String>().parse().unwrap();
-            let field_ty;
-            match get_function_param_ty(ty_size){
-                Ok(s) => field_ty = s,
+            let field_ty = match get_function_param_ty(ty_size){
+                Ok(s) => s,
                 Err(s) =>{
                     self.err = Some(syn::Error::new_spanned(field, s));
                     return;
                 }
-            }
+            };
             self.fields_seq.push((name,ty_size,field_ty));
             str_size += ty_size as usize;
         }
@@ -46,7 +46,7 @@ fn do_expand(st: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
         }                                            
     };                    
 
-    return Ok(ret);
+    Ok(ret)
 }
 type StructFields = syn::punctuated::Punctuated<syn::Field,syn::Token!(,)>;
 
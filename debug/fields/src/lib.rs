@@ -0,0 +1,23 @@
+// Crates that have the "proc-macro" crate type are only allowed to export
+// procedural macros, so the traits implemented by `#[derive(DebugFields)]` live
+// in this small support crate instead of in derive_debug.
+//
+// The derive walks the same field metadata as `CustomDebug`: skipped fields are
+// left out, and each value is handed over with its custom format or summary
+// already applied. That lets structured logging backends record every field by
+// name without parsing the `Debug` output.
+#![no_std]
+
+use core::fmt::Debug;
+
+/// Receives the fields of a value, one at a time and in declaration order.
+///
+/// Tuple struct fields are named by their index, `"0"`, `"1"` and so on.
+pub trait FieldVisitor {
+    fn visit_field(&mut self, name: &str, value: &dyn Debug);
+}
+
+/// Implemented by `#[derive(DebugFields)]`.
+pub trait DebugFields {
+    fn visit_fields(&self, visitor: &mut dyn FieldVisitor);
+}
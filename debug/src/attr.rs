@@ -0,0 +1,327 @@
+//! Parsing of the `#[debug ...]` and `#[display ...]` inert attributes.
+//!
+//! Accepted forms of `#[debug]`, on fields:
+//!
+//! ```text
+//! #[debug = "0b{:08b}"]
+//! #[debug(fmt = "0b{:08b}", bound = "T::Value: Debug", skip)]
+//! #[debug(truncate = 16, hex, len_only)]
+//! ```
+//!
+//! and on the container:
+//!
+//! ```text
+//! #[debug(bound = "T::Value: Debug", compact, transparent, max_depth = 8)]
+//! ```
+//!
+//! `#[display]` takes the format string of a struct or enum variant, plus the
+//! same `bound` escape hatch on the container and on fields:
+//!
+//! ```text
+//! #[display("{from} -> {to}", bound = "T: Display")]
+//! ```
+//!
+//! Any number of these attributes may appear, in any position among the
+//! other attributes. Every malformed or unknown entry is reported as a
+//! compile error pointing at the offending tokens.
+
+type Predicates = syn::punctuated::Punctuated<syn::WherePredicate, syn::Token!(,)>;
+
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// `#[debug = "..."]` or `#[debug(fmt = "...")]`
+    pub format: Option<syn::LitStr>,
+    /// `#[debug(bound = "...")]`, replacing the bounds inferred from this field
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    /// `#[debug(skip)]`, leaving the field out of the output entirely
+    pub skip: bool,
+    /// `#[debug(truncate = N)]`, showing only the first N elements
+    pub truncate: Option<usize>,
+    /// `#[debug(hex)]`, showing a byte collection as a hex string
+    pub hex: bool,
+    /// `#[debug(len_only)]`, showing only the number of elements
+    pub len_only: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// `#[debug(bound = "...")]`, replacing all inferred bounds
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    /// `#[debug(compact)]`, printing on one line even under `{:#?}`
+    pub compact: bool,
+    /// `#[debug(transparent)]`, printing the single field on its own
+    pub transparent: bool,
+    /// `#[debug(max_depth = N)]`, printing `...` past N nested levels
+    pub max_depth: Option<usize>,
+}
+
+/// `#[display]` 可以出现的位置
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum DisplayPosition {
+    Struct,
+    Enum,
+    Variant,
+    Field,
+}
+
+#[derive(Default)]
+pub(crate) struct DisplayAttrs {
+    /// `#[display("...")]`, on a struct or an enum variant
+    pub format: Option<syn::LitStr>,
+    /// `#[display(bound = "...")]`, on the container or a field
+    pub bound: Option<Vec<syn::WherePredicate>>,
+}
+
+/// One entry of a `#[debug]` or `#[display]` attribute.
+enum AttrItem {
+    /// `#[debug = "..."]` or `#[display("...")]`
+    Format(syn::LitStr),
+    /// one comma separated `key = "..."` or flag inside the parentheses
+    Nested(syn::Meta),
+}
+
+fn for_each_item(
+    attrs: &[syn::Attribute],
+    name: &str,
+    mut f: impl FnMut(AttrItem) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr in attrs {
+        if !attr.path.is_ident(name) {
+            continue;
+        }
+        match attr.parse_meta()? {
+            syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. }) => {
+                f(AttrItem::Format(s))?;
+            }
+            syn::Meta::NameValue(kv) => {
+                return Err(syn::Error::new_spanned(kv.lit, "expected string literal"));
+            }
+            syn::Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        syn::NestedMeta::Meta(meta) => f(AttrItem::Nested(meta))?,
+                        syn::NestedMeta::Lit(syn::Lit::Str(s)) => f(AttrItem::Format(s))?,
+                        syn::NestedMeta::Lit(lit) => {
+                            return Err(syn::Error::new_spanned(lit, "expected `key = \"...\"` or a flag"));
+                        }
+                    }
+                }
+            }
+            meta @ syn::Meta::Path(_) => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("expected `{0} = \"...\"` or `{0}(...)`", name),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut ret = FieldAttrs::default();
+    //用于报告互相冲突的选项
+    let mut summary_meta = None;
+    for_each_item(&field.attrs, "debug", |item| {
+        match item {
+            AttrItem::Format(s) => set_once(&mut ret.format, s.clone(), &s, "format string", "debug")?,
+            AttrItem::Nested(meta) => {
+                let key = meta_key(&meta);
+                match key.as_str() {
+                    "fmt" => {
+                        let s = expect_str(&meta)?;
+                        set_once(&mut ret.format, s, &meta, "format string", "debug")?;
+                    }
+                    "bound" => {
+                        let bound = parse_bound(&expect_str(&meta)?)?;
+                        set_once(&mut ret.bound, bound, &meta, "`bound`", "debug")?;
+                    }
+                    "skip" => {
+                        expect_flag(&meta)?;
+                        set_flag(&mut ret.skip, &meta, "debug")?;
+                    }
+                    "truncate" => {
+                        let n = expect_int(&meta)?;
+                        set_once(&mut ret.truncate, n, &meta, "`truncate`", "debug")?;
+                        summary_meta.get_or_insert(meta);
+                    }
+                    "hex" => {
+                        expect_flag(&meta)?;
+                        set_flag(&mut ret.hex, &meta, "debug")?;
+                        summary_meta.get_or_insert(meta);
+                    }
+                    "len_only" => {
+                        expect_flag(&meta)?;
+                        set_flag(&mut ret.len_only, &meta, "debug")?;
+                        summary_meta = Some(meta);
+                    }
+                    _ => {
+                        return Err(unknown_key(
+                            &meta,
+                            &key,
+                            "debug",
+                            &["fmt", "bound", "skip", "truncate", "hex", "len_only"],
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    if let Some(meta) = summary_meta {
+        if ret.format.is_some() {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "`truncate`, `hex` and `len_only` cannot be combined with a format string",
+            ));
+        }
+        if ret.len_only && (ret.truncate.is_some() || ret.hex) {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "`len_only` cannot be combined with `truncate` or `hex`",
+            ));
+        }
+    }
+    Ok(ret)
+}
+
+pub(crate) fn parse_container_attrs(attrs: &[syn::Attribute]) -> syn::Result<ContainerAttrs> {
+    let mut ret = ContainerAttrs::default();
+    for_each_item(attrs, "debug", |item| {
+        match item {
+            AttrItem::Format(s) => {
+                return Err(syn::Error::new_spanned(s, "format strings are only supported on fields"));
+            }
+            AttrItem::Nested(meta) => {
+                let key = meta_key(&meta);
+                match key.as_str() {
+                    "bound" => {
+                        let bound = parse_bound(&expect_str(&meta)?)?;
+                        set_once(&mut ret.bound, bound, &meta, "`bound`", "debug")?;
+                    }
+                    "compact" => {
+                        expect_flag(&meta)?;
+                        set_flag(&mut ret.compact, &meta, "debug")?;
+                    }
+                    "transparent" => {
+                        expect_flag(&meta)?;
+                        set_flag(&mut ret.transparent, &meta, "debug")?;
+                    }
+                    "max_depth" => {
+                        let n = expect_int(&meta)?;
+                        set_once(&mut ret.max_depth, n, &meta, "`max_depth`", "debug")?;
+                    }
+                    _ => {
+                        return Err(unknown_key(
+                            &meta,
+                            &key,
+                            "debug",
+                            &["bound", "compact", "transparent", "max_depth"],
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ret)
+}
+
+pub(crate) fn parse_display_attrs(attrs: &[syn::Attribute], position: DisplayPosition) -> syn::Result<DisplayAttrs> {
+    let takes_format = matches!(position, DisplayPosition::Struct | DisplayPosition::Variant);
+    let takes_bound = position != DisplayPosition::Variant;
+
+    let mut ret = DisplayAttrs::default();
+    for_each_item(attrs, "display", |item| {
+        match item {
+            AttrItem::Format(s) if takes_format => {
+                set_once(&mut ret.format, s.clone(), &s, "format string", "display")?;
+            }
+            AttrItem::Format(s) => {
+                return Err(syn::Error::new_spanned(
+                    s,
+                    "format strings are only supported on structs and enum variants",
+                ));
+            }
+            AttrItem::Nested(meta) => {
+                let key = meta_key(&meta);
+                match key.as_str() {
+                    "bound" if takes_bound => {
+                        let bound = parse_bound(&expect_str(&meta)?)?;
+                        set_once(&mut ret.bound, bound, &meta, "`bound`", "display")?;
+                    }
+                    _ if takes_bound => return Err(unknown_key(&meta, &key, "display", &["bound"])),
+                    _ => return Err(unknown_key(&meta, &key, "display", &[])),
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ret)
+}
+
+fn meta_key(meta: &syn::Meta) -> String {
+    let path = meta.path();
+    path.get_ident()
+        .map(|i| i.to_string())
+        .unwrap_or_else(|| quote::quote!(#path).to_string())
+}
+
+fn unknown_key(meta: &syn::Meta, key: &str, name: &str, expected: &[&str]) -> syn::Error {
+    let message = if expected.is_empty() {
+        format!("unknown {} attribute `{}`, only a format string is accepted here", name, key)
+    } else {
+        let expected = expected.iter().map(|k| format!("`{}`", k)).collect::<Vec<_>>().join(", ");
+        format!("unknown {} attribute `{}`, expected one of: {}", name, key, expected)
+    };
+    syn::Error::new_spanned(meta.path(), message)
+}
+
+fn expect_str(meta: &syn::Meta) -> syn::Result<syn::LitStr> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. }) => Ok(s.clone()),
+        syn::Meta::NameValue(kv) => Err(syn::Error::new_spanned(&kv.lit, "expected string literal")),
+        _ => Err(syn::Error::new_spanned(meta, format!("expected `{} = \"...\"`", meta_key(meta)))),
+    }
+}
+
+fn expect_int(meta: &syn::Meta) -> syn::Result<usize> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Int(n), .. }) => n.base10_parse(),
+        syn::Meta::NameValue(kv) => Err(syn::Error::new_spanned(&kv.lit, "expected integer literal")),
+        _ => Err(syn::Error::new_spanned(meta, format!("expected `{} = N`", meta_key(meta)))),
+    }
+}
+
+fn expect_flag(meta: &syn::Meta) -> syn::Result<()> {
+    match meta {
+        syn::Meta::Path(_) => Ok(()),
+        _ => Err(syn::Error::new_spanned(meta, format!("`{}` does not take a value", meta_key(meta)))),
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, span: impl quote::ToTokens, what: &str, name: &str) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(span, format!("duplicate {} in {} attribute", what, name)));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+fn set_flag(slot: &mut bool, meta: &syn::Meta, name: &str) -> syn::Result<()> {
+    if *slot {
+        return Err(syn::Error::new_spanned(
+            meta,
+            format!("duplicate `{}` in {} attribute", meta_key(meta), name),
+        ));
+    }
+    *slot = true;
+    Ok(())
+}
+
+/// 解析 `bound = "..."` 中的 where 子句谓词，出错时指向字符串字面量
+fn parse_bound(lit: &syn::LitStr) -> syn::Result<Vec<syn::WherePredicate>> {
+    let predicates = lit.parse_with(Predicates::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
@@ -0,0 +1,129 @@
+//! Trait bound inference for the generated impl.
+//!
+//! Rather than bounding every type parameter, each field type is walked with a
+//! `syn::visit` pass that records which type parameters it actually mentions,
+//! so that `struct S<T, TT> { a: TT }` only gets `TT: Debug`.
+//!
+//! Only type parameters are ever bounded. Lifetimes need no bound for `&'a T`
+//! to be `Debug` once `T` is, and const parameters are values: their uses in
+//! array lengths are expressions, which the visitor does not enter, and their
+//! uses as generic arguments such as `Bytes<N>` are ordinary paths that do
+//! not name a type parameter.
+
+use std::collections::HashSet;
+
+use syn::visit::Visit;
+
+pub(crate) struct Bounds {
+    /// 默认需要约束的 trait，如 `::core::fmt::Debug`
+    trait_path: syn::Path,
+    type_params: HashSet<syn::Ident>,
+    predicates: Vec<syn::WherePredicate>,
+}
+
+impl Bounds {
+    pub(crate) fn new(generics: &syn::Generics, trait_path: syn::Path) -> Self {
+        let type_params = generics.type_params().map(|t| t.ident.clone()).collect();
+        Bounds {
+            trait_path,
+            type_params,
+            predicates: Vec::new(),
+        }
+    }
+
+    /// 根据字段类型推断约束
+    pub(crate) fn infer_from_type(&mut self, ty: &syn::Type) {
+        let trait_path = self.trait_path.clone();
+        self.infer_from_type_as(ty, &trait_path);
+    }
+
+    /// 同上，但约束为指定的 trait，如 `{:x}` 占位符需要的 `LowerHex`
+    pub(crate) fn infer_from_type_as(&mut self, ty: &syn::Type, trait_path: &syn::Path) {
+        let mut visitor = TypeParamVisitor {
+            type_params: &self.type_params,
+            bounded_types: Vec::new(),
+        };
+        visitor.visit_type(ty);
+
+        for bounded_ty in visitor.bounded_types {
+            self.push(syn::parse_quote!(#bounded_ty: #trait_path));
+        }
+    }
+
+    /// 用户通过 `bound = "..."` 手写的约束
+    pub(crate) fn extend(&mut self, predicates: Vec<syn::WherePredicate>) {
+        for predicate in predicates {
+            self.push(predicate);
+        }
+    }
+
+    fn push(&mut self, predicate: syn::WherePredicate) {
+        if !self.predicates.contains(&predicate) {
+            self.predicates.push(predicate);
+        }
+    }
+
+    pub(crate) fn apply_to(self, generics: &mut syn::Generics) {
+        generics.make_where_clause().predicates.extend(self.predicates);
+    }
+}
+
+/// 找出类型中出现的泛型参数 `T` 与关联类型 `T::Value`、`<T as Trait>::Value`
+struct TypeParamVisitor<'a> {
+    type_params: &'a HashSet<syn::Ident>,
+    bounded_types: Vec<syn::Type>,
+}
+
+impl TypeParamVisitor<'_> {
+    fn is_type_param(&self, ty: &syn::Type) -> bool {
+        if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+            if let Some(ident) = path.get_ident() {
+                return self.type_params.contains(ident);
+            }
+        }
+        false
+    }
+
+    fn record(&mut self, ty: syn::Type) {
+        if !self.bounded_types.contains(&ty) {
+            self.bounded_types.push(ty);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for TypeParamVisitor<'_> {
+    fn visit_type_path(&mut self, node: &'ast syn::TypePath) {
+        match node.qself {
+            // <T as Trait>::Value
+            Some(ref qself) if self.is_type_param(&qself.ty) => {
+                self.record(syn::Type::Path(node.clone()));
+                return;
+            }
+            Some(_) => {}
+            None => {
+                if let Some(first) = node.path.segments.first() {
+                    if self.type_params.contains(&first.ident) {
+                        // `T` itself, or an associated type `T::Value`
+                        self.record(syn::Type::Path(node.clone()));
+                        return;
+                    }
+                }
+                // PhantomData<T> implements Debug whatever T is
+                if let Some(last) = node.path.segments.last() {
+                    if last.ident == "PhantomData" {
+                        return;
+                    }
+                }
+            }
+        }
+        syn::visit::visit_type_path(self, node);
+    }
+
+    // fn(T) -> U implements Debug whatever its argument and return types are
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    // 数组长度等表达式中不会出现需要约束的类型
+    fn visit_expr(&mut self, _: &'ast syn::Expr) {}
+
+    fn visit_type_macro(&mut self, _: &'ast syn::TypeMacro) {}
+}
@@ -0,0 +1,171 @@
+//! `#[derive(CustomDisplay)]`, sharing the attribute parsing and bound
+//! inference of `CustomDebug`.
+
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse_quote;
+
+use crate::attr::{self, DisplayPosition};
+use crate::bound::Bounds;
+use crate::format::{self, Piece};
+
+pub(crate) fn generate_display_trait(st: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name_ident = &st.ident;
+    let mut bounds = Bounds::new(&st.generics, parse_quote!(::core::fmt::Display));
+
+    let (container_bound, body) = match st.data {
+        syn::Data::Struct(ref data) => {
+            let container_attrs = attr::parse_display_attrs(&st.attrs, DisplayPosition::Struct)?;
+            let format = container_attrs.format.ok_or_else(|| {
+                syn::Error::new_spanned(name_ident, "missing `#[display(\"...\")]` format string")
+            })?;
+            let (pattern, write) = generate_write(quote!(Self), &data.fields, &format, &mut bounds)?;
+            (container_attrs.bound, quote!(
+                let #pattern = *self;
+                #write
+            ))
+        }
+        syn::Data::Enum(ref data) => {
+            let container_attrs = attr::parse_display_attrs(&st.attrs, DisplayPosition::Enum)?;
+            let mut arms = proc_macro2::TokenStream::new();
+            for variant in data.variants.iter() {
+                let variant_ident = &variant.ident;
+                let variant_attrs = attr::parse_display_attrs(&variant.attrs, DisplayPosition::Variant)?;
+                //单元变体默认输出变体名
+                let format = match (variant_attrs.format, &variant.fields) {
+                    (Some(format), _) => format,
+                    (None, syn::Fields::Unit) => syn::LitStr::new(&variant_ident.to_string(), variant_ident.span()),
+                    (None, _) => {
+                        return Err(syn::Error::new_spanned(
+                            variant,
+                            "missing `#[display(\"...\")]` format string on variant with fields",
+                        ))
+                    }
+                };
+                let (pattern, write) = generate_write(quote!(Self::#variant_ident), &variant.fields, &format, &mut bounds)?;
+                arms.extend(quote!(#pattern => #write,));
+            }
+            (container_attrs.bound, quote!(
+                match *self {
+                    #arms
+                }
+            ))
+        }
+        syn::Data::Union(ref data) => {
+            return Err(syn::Error::new_spanned(data.union_token, "CustomDisplay does not support unions"));
+        }
+    };
+
+    let mut generics_param_to_modify = st.generics.clone();
+    match container_bound {
+        Some(hatch) => generics_param_to_modify.make_where_clause().predicates.extend(hatch),
+        None => bounds.apply_to(&mut generics_param_to_modify),
+    }
+    let (impl_generics, type_generics, where_clause) = generics_param_to_modify.split_for_impl();
+
+    Ok(quote!(
+        impl #impl_generics ::core::fmt::Display for #name_ident #type_generics #where_clause {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    ))
+}
+
+/// 生成绑定格式串中用到的字段的模式，以及对应的 `write!`
+///
+/// Fields are referred to by name, `{from}`, or for tuple structs and variants
+/// by index, `{0}`. The pattern binds only the fields the format string uses,
+/// and bounds are inferred from those fields according to their placeholder,
+/// so `{0:x}` on a field of type `T` yields `T: LowerHex`.
+fn generate_write(
+    path: proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    format: &syn::LitStr,
+    bounds: &mut Bounds,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let format_value = format.value();
+    let mut referenced = vec![false; fields.len()];
+    let mut used: Vec<Vec<&'static str>> = vec![Vec::new(); fields.len()];
+    let mut rewritten = String::with_capacity(format_value.len());
+
+    for piece in format::parse_pieces(&format_value) {
+        let (arg, spec) = match piece {
+            Piece::Text(text) => {
+                rewritten.push_str(text);
+                continue;
+            }
+            Piece::Placeholder { arg, spec } => (arg, spec),
+        };
+        //格式串本身不允许原始标识符，这里两种写法都接受
+        let arg = arg.strip_prefix("r#").unwrap_or(arg);
+        let idx = match fields {
+            syn::Fields::Named(named) => named.named.iter().position(|f| f.ident.as_ref().unwrap().unraw() == arg),
+            syn::Fields::Unnamed(unnamed) => arg.parse::<usize>().ok().filter(|i| *i < unnamed.unnamed.len()),
+            syn::Fields::Unit => None,
+        };
+        let idx = match idx {
+            Some(idx) => idx,
+            None if arg.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    format,
+                    "positional `{}` placeholders are not supported, refer to fields by name or index",
+                ))
+            }
+            None => return Err(syn::Error::new_spanned(format, format!("no field `{}` to display", arg))),
+        };
+        referenced[idx] = true;
+        if let Some(trait_name) = format::trait_for_spec(spec) {
+            if !used[idx].contains(&trait_name) {
+                used[idx].push(trait_name);
+            }
+        }
+        let binding = binding_ident(fields, idx);
+        rewritten.push_str(&format::render_placeholder(&binding.to_string(), spec));
+    }
+    let format = syn::LitStr::new(&rewritten, format.span());
+
+    let mut bindings = Vec::new();
+    for (idx, field) in fields.iter().enumerate() {
+        let field_attrs = attr::parse_display_attrs(&field.attrs, DisplayPosition::Field)?;
+        //字段上的 bound 取代根据该字段类型推断出的约束
+        match field_attrs.bound {
+            Some(bound) => bounds.extend(bound),
+            None => {
+                for trait_name in used[idx].iter() {
+                    let trait_ident = format_ident!("{}", trait_name);
+                    bounds.infer_from_type_as(&field.ty, &parse_quote!(::core::fmt::#trait_ident));
+                }
+            }
+        }
+        bindings.push(if referenced[idx] { Some(binding_ident(fields, idx)) } else { None });
+    }
+
+    let pattern = match fields {
+        syn::Fields::Named(named) => {
+            let fields = named.named.iter().zip(bindings).filter_map(|(f, b)| {
+                let name = &f.ident;
+                b.map(|binding| quote!(#name: ref #binding))
+            });
+            quote!(#path { #(#fields,)* .. })
+        }
+        syn::Fields::Unnamed(_) => {
+            let elems = bindings.iter().map(|b| match b {
+                Some(ident) => quote!(ref #ident),
+                None => quote!(_),
+            });
+            quote!(#path(#(#elems),*))
+        }
+        syn::Fields::Unit => quote!(#path),
+    };
+    let write = quote!(::core::write!(__formatter, #format));
+    Ok((pattern, write))
+}
+
+/// 格式串中使用的绑定名，不能是 `r#type` 这样的原始标识符
+fn binding_ident(fields: &syn::Fields, idx: usize) -> syn::Ident {
+    match fields {
+        syn::Fields::Named(named) => format_ident!("__field_{}", named.named[idx].ident.as_ref().unwrap().unraw()),
+        _ => format_ident!("_{}", idx),
+    }
+}
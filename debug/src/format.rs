@@ -0,0 +1,146 @@
+//! Helpers for the format strings given in `#[debug = "..."]` and
+//! `#[display("...")]`.
+
+/// 格式串的一段：普通文本或 `{arg:spec}` 占位符
+pub(crate) enum Piece<'a> {
+    /// literal text, with `{{` and `}}` kept escaped
+    Text(&'a str),
+    Placeholder { arg: &'a str, spec: Option<&'a str> },
+}
+
+/// 把格式串切分成文本与占位符
+///
+/// Malformed strings are not reported here; whatever cannot be split is kept
+/// as text so that `format_args!` reports it against the original literal.
+pub(crate) fn parse_pieces(format: &str) -> Vec<Piece<'_>> {
+    let mut ret = Vec::new();
+    let mut rest = format;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        if pos > 0 {
+            ret.push(Piece::Text(&rest[..pos]));
+            rest = &rest[pos..];
+        }
+        // `{{` 与 `}}` 是转义的花括号
+        if rest.starts_with("{{") || rest.starts_with('}') {
+            let len = if rest.starts_with("{{") || rest.starts_with("}}") { 2 } else { 1 };
+            ret.push(Piece::Text(&rest[..len]));
+            rest = &rest[len..];
+            continue;
+        }
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let placeholder = &rest[1..end];
+        ret.push(match placeholder.split_once(':') {
+            Some((arg, spec)) => Piece::Placeholder { arg, spec: Some(spec) },
+            None => Piece::Placeholder { arg: placeholder, spec: None },
+        });
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        ret.push(Piece::Text(rest));
+    }
+    ret
+}
+
+pub(crate) fn render_placeholder(arg: &str, spec: Option<&str>) -> String {
+    match spec {
+        Some(spec) => format!("{{{}:{}}}", arg, spec),
+        None => format!("{{{}}}", arg),
+    }
+}
+
+/// 为格式串中的每个 `{:?}` 类占位符加上 `#` 标志，得到 `{:#?}` 下使用的格式串
+///
+/// Only debug placeholders are touched, since `#` changes the meaning of other
+/// specs (`{:#x}` prints a `0x` prefix). Returns `None` when no placeholder
+/// needs changing.
+pub(crate) fn alternate_format(format: &str) -> Option<String> {
+    let mut ret = String::with_capacity(format.len() + 2);
+    let mut changed = false;
+
+    for piece in parse_pieces(format) {
+        match piece {
+            Piece::Placeholder { arg, spec: Some(spec) } if spec.ends_with('?') => {
+                match with_alternate_flag(spec) {
+                    Some(spec) => {
+                        ret.push_str(&render_placeholder(arg, Some(&spec)));
+                        changed = true;
+                    }
+                    None => ret.push_str(&render_placeholder(arg, Some(spec))),
+                }
+            }
+            Piece::Placeholder { arg, spec } => ret.push_str(&render_placeholder(arg, spec)),
+            Piece::Text(text) => ret.push_str(text),
+        }
+    }
+
+    if changed {
+        Some(ret)
+    } else {
+        None
+    }
+}
+
+/// format_spec := [[fill]align][sign]['#']['0'][width]['.' precision]type
+fn with_alternate_flag(spec: &str) -> Option<String> {
+    let chars: Vec<char> = spec.chars().collect();
+    let is_align = |c: &char| matches!(c, '<' | '^' | '>');
+
+    let mut idx = if chars.len() > 1 && is_align(&chars[1]) {
+        2
+    } else if chars.first().is_some_and(is_align) {
+        1
+    } else {
+        0
+    };
+    if matches!(chars.get(idx), Some('+') | Some('-')) {
+        idx += 1;
+    }
+    if chars.get(idx) == Some(&'#') {
+        return None;
+    }
+
+    let mut ret: String = chars[..idx].iter().collect();
+    ret.push('#');
+    ret.extend(&chars[idx..]);
+    Some(ret)
+}
+
+/// 格式串中各占位符所需的 trait，去掉重复
+pub(crate) fn traits_for_format(format: &str) -> Vec<&'static str> {
+    let mut ret = Vec::new();
+    for piece in parse_pieces(format) {
+        if let Piece::Placeholder { spec, .. } = piece {
+            if let Some(trait_name) = trait_for_spec(spec) {
+                if !ret.contains(&trait_name) {
+                    ret.push(trait_name);
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// 占位符格式所需的 trait，如 `{:x}` 需要 `LowerHex`
+///
+/// Placeholders are formatted through a reference to the field, so `{:p}`
+/// needs no bound at all.
+pub(crate) fn trait_for_spec(spec: Option<&str>) -> Option<&'static str> {
+    let spec = spec.unwrap_or("");
+    if spec.ends_with('?') {
+        return Some("Debug");
+    }
+    match spec.chars().last() {
+        Some('x') => Some("LowerHex"),
+        Some('X') => Some("UpperHex"),
+        Some('o') => Some("Octal"),
+        Some('b') => Some("Binary"),
+        Some('e') => Some("LowerExp"),
+        Some('E') => Some("UpperExp"),
+        Some('p') => None,
+        _ => Some("Display"),
+    }
+}
@@ -0,0 +1,96 @@
+//! Wrapper types emitted inside the generated `fmt` body.
+//!
+//! The derive cannot ship runtime code of its own, so each wrapper a struct
+//! needs is defined as a local item of its `fmt` function and only emitted
+//! when at least one field uses it.
+
+use quote::quote;
+
+#[derive(Default)]
+pub(crate) struct Helpers {
+    /// 按 `fmt.alternate()` 选择格式串的自定义格式
+    pub debug_with: bool,
+    /// `#[debug(truncate = N)]`
+    pub truncate: bool,
+    /// `#[debug(hex)]`
+    pub hex: bool,
+    /// `#[debug(len_only)]`
+    pub len: bool,
+}
+
+impl Helpers {
+    pub(crate) fn definitions(&self) -> proc_macro2::TokenStream {
+        let mut ret = proc_macro2::TokenStream::new();
+        if self.debug_with {
+            ret.extend(quote!(
+                struct __DebugWith<F: ::core::ops::Fn(&mut ::core::fmt::Formatter<'_>)-> ::core::fmt::Result>(F);
+                impl<F: ::core::ops::Fn(&mut ::core::fmt::Formatter<'_>)-> ::core::fmt::Result> ::core::fmt::Debug for __DebugWith<F>{
+                    fn fmt(&self,f:&mut ::core::fmt::Formatter<'_>)-> ::core::fmt::Result{
+                        (self.0)(f)
+                    }
+                }
+            ));
+        }
+        if self.truncate {
+            ret.extend(quote!(
+                struct __DebugTruncate<'a,C: ?::core::marker::Sized>(&'a C,::core::primitive::usize);
+                impl<'a,C: ?::core::marker::Sized> ::core::fmt::Debug for __DebugTruncate<'a,C>
+                where
+                    &'a C: ::core::iter::IntoIterator,
+                    <&'a C as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+                {
+                    fn fmt(&self,f:&mut ::core::fmt::Formatter<'_>)-> ::core::fmt::Result{
+                        let mut list = f.debug_list();
+                        let mut iter = ::core::iter::IntoIterator::into_iter(self.0);
+                        for item in ::core::iter::Iterator::take(&mut iter,self.1){
+                            list.entry(&item);
+                        }
+                        let rest = ::core::iter::Iterator::count(iter);
+                        if rest > 0{
+                            list.entry(&::core::format_args!("... ({} more)",rest));
+                        }
+                        list.finish()
+                    }
+                }
+            ));
+        }
+        if self.hex {
+            ret.extend(quote!(
+                struct __DebugHex<'a,C: ?::core::marker::Sized>(&'a C,::core::option::Option<::core::primitive::usize>);
+                impl<'a,C: ?::core::marker::Sized> ::core::fmt::Debug for __DebugHex<'a,C>
+                where
+                    &'a C: ::core::iter::IntoIterator,
+                    <&'a C as ::core::iter::IntoIterator>::Item: ::core::borrow::Borrow<::core::primitive::u8>,
+                {
+                    fn fmt(&self,f:&mut ::core::fmt::Formatter<'_>)-> ::core::fmt::Result{
+                        let limit = ::core::option::Option::unwrap_or(self.1,::core::primitive::usize::MAX);
+                        let mut iter = ::core::iter::IntoIterator::into_iter(self.0);
+                        for byte in ::core::iter::Iterator::take(&mut iter,limit){
+                            ::core::write!(f,"{:02x}",*::core::borrow::Borrow::<::core::primitive::u8>::borrow(&byte))?;
+                        }
+                        let rest = ::core::iter::Iterator::count(iter);
+                        if rest > 0{
+                            ::core::write!(f,"... ({} more)",rest)?;
+                        }
+                        ::core::result::Result::Ok(())
+                    }
+                }
+            ));
+        }
+        if self.len {
+            ret.extend(quote!(
+                struct __DebugLen<'a,C: ?::core::marker::Sized>(&'a C);
+                impl<'a,C: ?::core::marker::Sized> ::core::fmt::Debug for __DebugLen<'a,C>
+                where
+                    &'a C: ::core::iter::IntoIterator,
+                {
+                    fn fmt(&self,f:&mut ::core::fmt::Formatter<'_>)-> ::core::fmt::Result{
+                        let len = ::core::iter::Iterator::count(::core::iter::IntoIterator::into_iter(self.0));
+                        ::core::write!(f,"len={}",len)
+                    }
+                }
+            ));
+        }
+        ret
+    }
+}
@@ -1,8 +1,12 @@
-use std::{collections::HashMap};
+mod attr;
+mod bound;
+mod display;
+mod format;
+mod helpers;
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_quote, visit::Visit};
+use quote::{format_ident, quote};
+use syn::parse_quote;
 
 
 #[proc_macro_derive(CustomDebug,attributes(debug))]
@@ -16,254 +20,250 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
 }
 
+#[proc_macro_derive(CustomDisplay,attributes(display))]
+pub fn derive_display(input: TokenStream) -> TokenStream {
+    let st = syn::parse_macro_input!(input as syn::DeriveInput);
+    match display::generate_display_trait(&st){
+        Ok(token_stream)=> token_stream.into(),
+        Err(e)=>e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(DebugFields,attributes(debug))]
+pub fn derive_debug_fields(input: TokenStream) -> TokenStream {
+    let st = syn::parse_macro_input!(input as syn::DeriveInput);
+    match generate_debug_fields_trait(&st){
+        Ok(token_stream)=> token_stream.into(),
+        Err(e)=>e.to_compile_error().into(),
+    }
+}
+
 fn do_expand(st:&syn::DeriveInput)->syn::Result<proc_macro2::TokenStream>{
     let ret =generate_debug_trait(st)?;
     
-    return Ok(ret);
+    Ok(ret)
 }
 
-type StructFields = syn::punctuated::Punctuated<syn::Field,syn::Token!(,)>;
-
-fn get_fields_from_derive_input(d:&syn::DeriveInput)->syn::Result<&StructFields>{
-    if let syn::Data::Struct(syn::DataStruct{
-        fields: syn::Fields::Named(syn::FieldsNamed{ref named,..}),
-        ..
-    }) = d.data{
-        return Ok(named)
+fn get_fields_from_derive_input(d:&syn::DeriveInput)->syn::Result<&syn::Fields>{
+    if let syn::Data::Struct(syn::DataStruct{ref fields,..}) = d.data{
+        return Ok(fields)
     }
     Err(syn::Error::new_spanned(d, "Must define on a struct,not Enum".to_string()))
 }
 
-fn generate_debug_trait(st:&syn::DeriveInput)-> syn::Result<proc_macro2::TokenStream>{
-    let fields = get_fields_from_derive_input(st)?;
-    let struct_name_ident = &st.ident;
-
-    let struct_name_literal = struct_name_ident.to_string();
-    let mut fmt_body_stream = proc_macro2::TokenStream::new();
-
-    fmt_body_stream.extend(quote!(
-        debug_struct(#struct_name_literal)
-    ));
-
-    let mut user_specified_generics = Vec::new();
-    for field in fields.iter(){
-        let field_name_ident = field.ident.as_ref().unwrap();
-        let field_name_literal = field_name_ident.to_string();
+/// CustomDebug 与 DebugFields 共用的字段信息
+struct DebugInfo<'a>{
+    fields:&'a syn::Fields,
+    container_attrs:attr::ContainerAttrs,
+    /// 未被跳过的字段及其 `&dyn Debug` 取值表达式
+    debug_fields:Vec<(syn::Member,proc_macro2::TokenStream)>,
+    helpers:helpers::Helpers,
+    /// 已加上推断或手写约束的泛型
+    generics:syn::Generics,
+}
 
-        let mut format_str = "{:?}".to_string();
-        if let Some(format) = get_custom_format_of_field(field)?{
-            format_str = format;
+fn collect_debug_info(st:&syn::DeriveInput)-> syn::Result<DebugInfo<'_>>{
+    let fields = get_fields_from_derive_input(st)?;
+    let container_attrs = attr::parse_container_attrs(&st.attrs)?;
+
+    let mut helpers = helpers::Helpers::default();
+    let mut bounds = bound::Bounds::new(&st.generics,parse_quote!(::core::fmt::Debug));
+    let mut debug_fields = Vec::new();
+    for (idx,field) in fields.iter().enumerate(){
+        let field_attrs = attr::parse_field_attrs(field)?;
+        if field_attrs.skip{
+            continue;
         }
-        
-        if let Some(field_generic) = get_user_specified_field_generic(field)?{
-            if !user_specified_generics.contains(&field_generic){
-                user_specified_generics.push(field_generic);
+
+        //字段上的 bound 取代根据该字段类型推断出的约束
+        //hex 与 len_only 不会用到元素的 Debug，自定义格式则按占位符所需的 trait 约束
+        match (&field_attrs.bound,&field_attrs.format){
+            (Some(bound),_) => bounds.extend(bound.clone()),
+            (None,_) if field_attrs.hex || field_attrs.len_only => {}
+            (None,Some(format)) => {
+                for trait_name in format::traits_for_format(&format.value()){
+                    let trait_ident = format_ident!("{}",trait_name);
+                    bounds.infer_from_type_as(&field.ty,&parse_quote!(::core::fmt::#trait_ident));
+                }
             }
+            (None,None) => bounds.infer_from_type(&field.ty),
         }
 
-        fmt_body_stream.extend(quote!{
-           .field(#field_name_literal,&format_args!(#format_str,self.#field_name_ident))
-        });
+        let member = match field.ident{
+            Some(ref ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(idx.into()),
+        };
+        let value = generate_field_value(&member,&field.ty,&field_attrs,&mut helpers);
+        debug_fields.push((member,value));
     }
 
-    fmt_body_stream.extend(quote!(
-        .finish()
-    ));
-
     let mut generics_param_to_modify = st.generics.clone();
+    match container_attrs.bound{
+        Some(ref hatch) => generics_param_to_modify.make_where_clause().predicates.extend(hatch.clone()),
+        None => bounds.apply_to(&mut generics_param_to_modify),
+    }
+
+    Ok(DebugInfo{
+        fields,
+        container_attrs,
+        debug_fields,
+        helpers,
+        generics:generics_param_to_modify,
+    })
+}
 
-    let mut field_type_names = Vec::new();//字段类型集合
-    let mut phantomdata_type_param_names = Vec::new();//phantomdata泛型类型集合
+fn generate_debug_trait(st:&syn::DeriveInput)-> syn::Result<proc_macro2::TokenStream>{
+    let DebugInfo{fields,container_attrs,debug_fields,helpers,generics} = collect_debug_info(st)?;
+    let struct_name_ident = &st.ident;
+    let struct_name_literal = struct_name_ident.to_string();
 
-    for field in fields{
-        if let Some(s) = get_field_type_name(field)?{
-            field_type_names.push(s);
+    let fmt_body_stream = if container_attrs.transparent{
+        //只有一个字段时直接委托给该字段
+        if debug_fields.len() != 1{
+            return Err(syn::Error::new_spanned(
+                struct_name_ident,
+                format!("#[debug(transparent)] requires exactly one non-skipped field, found {}",debug_fields.len()),
+            ));
         }
-        if let Some(s) = get_phantomdata_generic_type_name(field)?{
-            phantomdata_type_param_names.push(s);
+        let value = &debug_fields[0].1;
+        quote!(::core::fmt::Debug::fmt(#value,fmt))
+    }else{
+        match fields{
+            syn::Fields::Named(_) => {
+                let names = debug_fields.iter().map(|(member,_)| match member{
+                    syn::Member::Named(ident) => ident.to_string(),
+                    syn::Member::Unnamed(_) => unreachable!(),
+                });
+                let values = debug_fields.iter().map(|(_,value)| value);
+                quote!(
+                    fmt.debug_struct(#struct_name_literal)
+                        #(.field(#names,#values))*
+                        .finish()
+                )
+            }
+            syn::Fields::Unnamed(_) => {
+                let values = debug_fields.iter().map(|(_,value)| value);
+                quote!(
+                    fmt.debug_tuple(#struct_name_literal)
+                        #(.field(#values))*
+                        .finish()
+                )
+            }
+            syn::Fields::Unit => quote!(fmt.write_str(#struct_name_literal)),
         }
-    }
+    };
 
-    if let Some(hatch) = get_struct_escape_hatch(st){
-        generics_param_to_modify.make_where_clause();
-        generics_param_to_modify
-            .where_clause
-            .as_mut()
-            .unwrap()
-            .predicates
-            .push(syn::parse_str(hatch.as_str()).unwrap());
-    }else {
-        let associated_type_map = get_generic_associated_types(st);
-        for g in generics_param_to_modify.params.iter_mut(){//为泛型添加debug约束
-            if let syn::GenericParam::Type(t) = g{
-                let type_param_name = t.ident.to_string();
-                // eprintln!("type_param_name:{}",type_param_name);
-                //phantomdata中有字段类型没有则跳过
-                if phantomdata_type_param_names.contains(&type_param_name) && !field_type_names.contains(&type_param_name){
-                    continue;
-                }
-                //
-                if associated_type_map.contains_key(&type_param_name) && !field_type_names.contains(&type_param_name){
-                    continue;
-                }
+    let (impl_generics,type_generics,where_clause) = generics.split_for_impl();
 
-                let mut keep = false;
-                for generic in user_specified_generics.iter(){
-                    if generic[..type_param_name.len()] == *type_param_name.as_str(){
-                        keep = true;
-                        break;
-                    }
-                }
-                if keep{
-                    continue;
-                }
-    
-                t.bounds.push(parse_quote!(std::fmt::Debug));
+    let mut prelude_stream = proc_macro2::TokenStream::new();
+    if container_attrs.compact{
+        //{:#?} 下退回到单行输出
+        prelude_stream.extend(quote!(
+            if fmt.alternate(){
+                return ::core::write!(fmt,"{:?}",self);
             }
-        }
-    
-        generics_param_to_modify.make_where_clause();
-        let  predicates =  &mut generics_param_to_modify
-            .where_clause
-            .as_mut()
-            .unwrap()
-            .predicates;
-
-        if !user_specified_generics.is_empty(){
-            
-            for generic in user_specified_generics{
-                predicates.push(syn::parse_str(generic.as_str()).unwrap());
+        ));
+    }
+    if let Some(max_depth) = container_attrs.max_depth{
+        //用线程局部计数器记录当前嵌套层数，超过后输出 `...`
+        //thread_local! 需要 std，因此只在使用 max_depth 时引入
+        prelude_stream.extend(quote!(
+            ::std::thread_local!{
+                static __DEBUG_DEPTH: ::core::cell::Cell<::core::primitive::usize> = const { ::core::cell::Cell::new(0) };
             }
-        }
-        for (_,associated_types) in associated_type_map{
-            for associated_type in associated_types{
-                predicates.push(parse_quote!(#associated_type:std::fmt::Debug));
+            struct __DebugDepthGuard;
+            impl ::core::ops::Drop for __DebugDepthGuard{
+                fn drop(&mut self){
+                    __DEBUG_DEPTH.with(|depth| depth.set(depth.get() - 1));
+                }
             }
-        }
+            let __depth = __DEBUG_DEPTH.with(|depth|{
+                let current = depth.get();
+                depth.set(current + 1);
+                current
+            });
+            let _guard = __DebugDepthGuard;
+            if __depth >= #max_depth{
+                return fmt.write_str("...");
+            }
+        ));
     }
-
-    let (impl_generics,type_generics,where_clause) = generics_param_to_modify.split_for_impl();
+    prelude_stream.extend(helpers.definitions());
 
     let ret_stream = quote!(
-        impl #impl_generics std::fmt::Debug for #struct_name_ident #type_generics #where_clause{
-            fn fmt(&self,fmt:&mut std::fmt::Formatter)-> std::fmt::Result{
-                fmt.#fmt_body_stream
+        impl #impl_generics ::core::fmt::Debug for #struct_name_ident #type_generics #where_clause{
+            fn fmt(&self,fmt:&mut ::core::fmt::Formatter)-> ::core::fmt::Result{
+                #prelude_stream
+                #fmt_body_stream
             }
         }
     );
-    return Ok(ret_stream)
+    Ok(ret_stream)
 }
 
-fn get_custom_format_of_field(field:&syn::Field)->syn::Result<Option<String>>{
-    for attr in &field.attrs{
-        let attr_mate = attr.parse_meta();
-        // eprintln!("attr_parse_meta:\n{:#?}",attr.parse_meta()?);
-        if let Ok(syn::Meta::NameValue(syn::MetaNameValue{
-            ref path,
-            ref lit,
-            ..
-        })) = attr_mate{
-            if path.is_ident("debug"){
-                if let syn::Lit::Str(ref ident_str) = lit{
-                    return Ok(Some(ident_str.value()));
-                }
-            }
-        }
-    }
-
-    Ok(None)
-}
+/// 为结构化日志生成 `::debug_fields::DebugFields`，逐个交出字段名与取值
+fn generate_debug_fields_trait(st:&syn::DeriveInput)-> syn::Result<proc_macro2::TokenStream>{
+    let DebugInfo{debug_fields,helpers,generics,..} = collect_debug_info(st)?;
+    let struct_name_ident = &st.ident;
 
-fn get_user_specified_field_generic(field:&syn::Field)->syn::Result<Option<String>>{
-    for attr in &field.attrs{
-        if let Ok(syn::Meta::List(syn::MetaList{
-            ref nested,
-            ..
-        })) = attr.parse_meta(){
-            if let Some(syn::NestedMeta::Meta(syn::Meta::NameValue(kv))) = nested.first(){
-                if kv.path.is_ident("bound"){
-                    if let syn::Lit::Str(ref ident_str) = kv.lit{
-                        return Ok(Some(ident_str.value()));
-                    }
-                }
+    let names = debug_fields.iter().map(|(member,_)| match member{
+        syn::Member::Named(ident) => ident.to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    });
+    let values = debug_fields.iter().map(|(_,value)| value);
+    let helper_definitions = helpers.definitions();
+
+    let (impl_generics,type_generics,where_clause) = generics.split_for_impl();
+    Ok(quote!(
+        impl #impl_generics ::debug_fields::DebugFields for #struct_name_ident #type_generics #where_clause{
+            fn visit_fields(&self,visitor:&mut dyn ::debug_fields::FieldVisitor){
+                #helper_definitions
+                #(visitor.visit_field(#names,#values);)*
             }
         }
-    }
-    Ok(None)
+    ))
 }
 
-fn get_phantomdata_generic_type_name(field: &syn::Field) -> syn::Result<Option<String>> {
-    if let syn::Type::Path(syn::TypePath{path: syn::Path{ref segments,..},..}) = field.ty{
-        if let Some(syn::PathSegment{ref ident,ref arguments}) = segments.last(){
-            if ident == "PhantomData"{
-                if let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments{args,..}) = arguments{
-                    if let Some(syn::GenericArgument::Type(syn::Type::Path(ref gp))) = args.first(){
-                        if let Some(generic_ident) = gp.path.segments.first(){
-                            return Ok(Some(generic_ident.ident.to_string()));
-                        }
-                    }
-                }
-            }
-        }
+/// 生成字段的 `&dyn Debug` 取值表达式，按需套上自定义格式或摘要包装类型
+fn generate_field_value(member:&syn::Member,ty:&syn::Type,field_attrs:&attr::FieldAttrs,helpers:&mut helpers::Helpers)->proc_macro2::TokenStream{
+    //引用类型的字段（如 `&'a [u8]`）本身已是集合的引用
+    let collection = match ty{
+        syn::Type::Reference(_) => quote!(&*self.#member),
+        _ => quote!(&self.#member),
+    };
+    if field_attrs.len_only{
+        helpers.len = true;
+        return quote!(&__DebugLen(#collection));
     }
-    return Ok(None);
-}
-
-fn get_field_type_name(field:&syn::Field)-> syn::Result<Option<String>>{
-    if let syn::Type::Path(syn::TypePath{path: syn::Path{ref segments,..},..}) = field.ty{
-        if let Some(syn::PathSegment{ref ident,..}) = segments.last(){
-            return Ok(Some(ident.to_string()));
-        }
+    if field_attrs.hex{
+        helpers.hex = true;
+        let limit = match field_attrs.truncate{
+            Some(n) => quote!(::core::option::Option::Some(#n)),
+            None => quote!(::core::option::Option::None),
+        };
+        return quote!(&__DebugHex(#collection,#limit));
     }
-    return Ok(None);
-}
-
-struct  TypePathVisitor{
-    generic_type_names:Vec<String>,
-    associated_type:HashMap<String,Vec<syn::TypePath>>,
-}
-
-impl<'a> Visit<'a> for TypePathVisitor{
-    fn visit_type_path(&mut self,node:&'a syn::TypePath){
-        if node.path.segments.len() >= 2{
-            let generic_type_name = node.path.segments[0].ident.to_string();
-            if self.generic_type_names.contains(&generic_type_name){
-                self.associated_type.entry(generic_type_name).or_insert(Vec::new()).push(node.clone());
-            }
-        }
-        syn::visit::visit_type_path(self,node); 
+    if let Some(n) = field_attrs.truncate{
+        helpers.truncate = true;
+        return quote!(&__DebugTruncate(#collection,#n));
     }
-}
 
-/// 获取所有泛型关联类型
-fn get_generic_associated_types(st: &syn::DeriveInput)->HashMap<String,Vec<syn::TypePath>>{
-    let origin_generic_param_name:Vec<String> = st.generics.params.iter().filter_map(|f|{
-        if let syn::GenericParam::Type(ty) = f{
-            return Some(ty.ident.to_string());
-        }
-        return None;
-    }).collect();
-
-    let mut visitor = TypePathVisitor{
-        generic_type_names:origin_generic_param_name,
-        associated_type:HashMap::new(),
+    let format = match field_attrs.format{
+        Some(ref format) => format,
+        None => return quote!(&self.#member),
     };
-
-    visitor.visit_derive_input(st);
-    return visitor.associated_type;
-}
-
-fn get_struct_escape_hatch(st:&syn::DeriveInput)->Option<String>{
-    if let Some(inert_attr) = st.attrs.last(){
-        if let Ok(syn::Meta::List(syn::MetaList{nested,..})) = inert_attr.parse_meta(){
-            if let Some(syn::NestedMeta::Meta(syn::Meta::NameValue(path_value))) = nested.last(){
-                if path_value.path.is_ident("bound"){
-                    if let syn::Lit::Str(ref lit) = path_value.lit{
-                        return Some(lit.value());
-                    }
+    match format::alternate_format(&format.value()){
+        Some(alternate) => {
+            let alternate = syn::LitStr::new(&alternate,format.span());
+            helpers.debug_with = true;
+            quote!(&__DebugWith(|f: &mut ::core::fmt::Formatter<'_>|{
+                if f.alternate(){
+                    ::core::write!(f,#alternate,self.#member)
+                }else{
+                    ::core::write!(f,#format,self.#member)
                 }
-            }
+            }))
         }
+        None => quote!(&::core::format_args!(#format,self.#member)),
     }
-    None
-}
\ No newline at end of file
+}
@@ -0,0 +1,65 @@
+// The `#[debug]` attribute may appear any number of times and in any position
+// among a field's or container's other attributes. Besides the `#[debug =
+// "..."]` shorthand, the list form accepts:
+//
+//   - `fmt = "..."`, the same as the shorthand;
+//   - `bound = "..."`, replacing inferred trait bounds;
+//   - `skip`, leaving the field out of the output.
+//
+// Here the escape hatch is followed by an unrelated attribute, which must not
+// cause the handwritten bound to be dropped.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+#[debug(bound = "T::Value: Debug")]
+#[allow(dead_code)]
+pub struct Wrapper<T: Trait> {
+    field: Field<T>,
+    #[debug(skip)]
+    secret: u64,
+}
+
+#[derive(CustomDebug)]
+struct Field<T: Trait> {
+    values: Vec<T::Value>,
+}
+
+#[derive(CustomDebug)]
+pub struct Register {
+    #[allow(dead_code)]
+    #[debug(fmt = "0b{:08b}")]
+    bitmask: u8,
+    #[debug(skip)]
+    #[doc = "not shown"]
+    cache: Vec<u8>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Wrapper<Id>>();
+
+    let w = Wrapper::<Id> {
+        field: Field { values: vec![1, 2] },
+        secret: 42,
+    };
+    assert_eq!(format!("{:?}", w), "Wrapper { field: Field { values: [1, 2] } }");
+
+    let r = Register {
+        bitmask: 0b00011100,
+        cache: vec![1, 2, 3],
+    };
+    assert_eq!(format!("{:?}", r), "Register { bitmask: 0b00011100 }");
+}
@@ -0,0 +1,61 @@
+// Malformed `#[debug]` and `#[display]` attributes are reported as compile
+// errors pointing at the offending tokens, rather than being silently ignored.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+#[derive(CustomDebug)]
+pub struct UnknownKey {
+    #[debug(format = "{:x}")]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct BadBound<T> {
+    #[debug(bound = "T Debug")]
+    value: T,
+}
+
+#[derive(CustomDebug)]
+#[debug = "{:?}"]
+pub struct FormatOnContainer {
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Duplicate {
+    #[debug = "{:x}"]
+    #[debug(fmt = "{:o}")]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct FlagWithValue {
+    #[debug(skip = "yes")]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct NotNewtype {
+    a: u8,
+    b: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct SummaryConflict {
+    #[debug(len_only, truncate = 4)]
+    value: Vec<u8>,
+}
+
+#[derive(CustomDisplay)]
+pub struct MissingFormat {
+    value: u8,
+}
+
+#[derive(CustomDisplay)]
+#[display("{value} {missing}")]
+pub struct UnknownField {
+    value: u8,
+}
+
+fn main() {}
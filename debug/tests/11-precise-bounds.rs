@@ -0,0 +1,99 @@
+// Bounds are inferred from the type parameters each field type actually
+// mentions, found by walking the field type with a syn::visit pass. This looks
+// through references, tuples, arrays, boxes and nested generic arguments, and
+// does not confuse parameters whose names share a prefix such as `T` and `TT`.
+//
+// For the struct below the generated impl needs to look like:
+//
+//     impl<'a, T, TT, A, B, C, D, E, P, S, K: Trait, Q: Trait> Debug for Everything<...>
+//     where
+//         TT: Debug,
+//         A: Debug,
+//         B: Debug,
+//         C: Debug,
+//         D: Debug,
+//         E: Debug,
+//         K::Value: Debug,
+//         <Q as Trait>::Value: Debug,
+//
+// with no bound at all on `T`, `P`, `S`, `K` or `Q`, which are only mentioned
+// in a PhantomData, a skipped field, a function pointer or through an
+// associated type.
+//
+// A field with a custom format is bounded by the traits its placeholders
+// need instead of Debug, so `Hex` below only requires `T: LowerHex`.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+#[allow(dead_code)]
+pub struct Everything<'a, T, TT, A, B, C, D, E, P, S, K: Trait, Q: Trait> {
+    tt: TT,
+    reference: &'a A,
+    tuple: (B, C),
+    array: [Box<D>; 2],
+    nested: Option<Vec<Box<E>>>,
+    marker: PhantomData<&'a (T, P)>,
+    #[debug(skip)]
+    skipped: S,
+    callback: fn(T) -> T,
+    assoc: Vec<K::Value>,
+    qualified: Option<<Q as Trait>::Value>,
+}
+
+#[derive(CustomDebug)]
+pub struct Hex<T> {
+    #[debug = "{:#x}"]
+    value: T,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    struct Id;
+
+    struct OnlyHex;
+
+    impl std::fmt::LowerHex for OnlyHex {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("0xff")
+        }
+    }
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Everything<NotDebug, u8, u8, u8, u8, u8, u8, NotDebug, NotDebug, Id, Id>>();
+
+    let value = 1u8;
+    let e = Everything::<NotDebug, u8, u8, u8, u8, u8, u8, NotDebug, NotDebug, Id, Id> {
+        tt: 0,
+        reference: &value,
+        tuple: (2, 3),
+        array: [Box::new(4), Box::new(5)],
+        nested: Some(vec![Box::new(6)]),
+        marker: PhantomData,
+        skipped: NotDebug,
+        callback: |t| t,
+        assoc: vec![7],
+        qualified: None,
+    };
+    let debug = format!("{:?}", e);
+    assert!(debug.starts_with(
+        "Everything { tt: 0, reference: 1, tuple: (2, 3), array: [4, 5], nested: Some([6]), marker: PhantomData"
+    ));
+    assert!(debug.ends_with("assoc: [7], qualified: None }"));
+
+    assert_debug::<Hex<OnlyHex>>();
+    assert_eq!(format!("{:?}", Hex { value: OnlyHex }), "Hex { value: 0xff }");
+    assert_eq!(format!("{:?}", Hex { value: 255u8 }), "Hex { value: 0xff }");
+}
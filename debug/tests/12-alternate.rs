@@ -0,0 +1,73 @@
+// Custom format strings are applied through format_args!, which on its own
+// ignores whether the caller asked for `{:#?}`. Fields whose format string
+// contains a debug placeholder are instead printed with `{:#?}` in its place
+// when the outer formatter is in alternate mode, so that the whole value
+// pretty-prints consistently.
+//
+// A container can also opt out of pretty printing with `#[debug(compact)]`,
+// which keeps it on a single line even under `{:#?}`. That is convenient for
+// small values that end up inside log lines.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+pub struct Shape {
+    #[debug = "points={:?}"]
+    points: Vec<Point>,
+    #[debug = "0b{:08b}"]
+    flags: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub struct Compact {
+    a: u8,
+    b: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+pub struct Outer {
+    inner: Compact,
+}
+
+fn main() {
+    let shape = Shape {
+        points: vec![Point { x: 1, y: 2 }],
+        flags: 0b101,
+    };
+    assert_eq!(
+        format!("{:?}", shape),
+        "Shape { points: points=[Point { x: 1, y: 2 }], flags: 0b00000101 }",
+    );
+    assert_eq!(
+        format!("{:#?}", shape),
+        "\
+Shape {
+    points: points=[
+        Point {
+            x: 1,
+            y: 2,
+        },
+    ],
+    flags: 0b00000101,
+}",
+    );
+
+    let compact = Compact { a: 1, b: vec![2, 3] };
+    assert_eq!(format!("{:#?}", compact), "Compact { a: 1, b: [2, 3] }");
+
+    let outer = Outer { inner: compact };
+    assert_eq!(
+        format!("{:#?}", outer),
+        "\
+Outer {
+    inner: Compact { a: 1, b: [2, 3] },
+}",
+    );
+}
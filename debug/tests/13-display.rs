@@ -0,0 +1,99 @@
+// CustomDisplay is a companion derive for Display that shares the attribute
+// parsing and trait bound inference of CustomDebug.
+//
+// The format string goes in `#[display("...")]` on a struct or on each enum
+// variant, and refers to fields by name or, for tuple structs and variants, by
+// index. Unit variants without an attribute display as their own name.
+// A raw identifier field such as `r#type` is referred to as `{type}`, or as
+// `{r#type}`, which format strings themselves do not accept.
+//
+// Bounds are inferred only from the fields the format string mentions, using
+// the trait its placeholder needs: `{0:x}` on a field of type `T` yields
+// `T: LowerHex`. The `#[display(bound = "...")]` escape hatch works the same
+// way as `#[debug(bound = "...")]`.
+
+use derive_debug::CustomDisplay;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+#[derive(CustomDisplay)]
+#[display("{from} -> {to}")]
+pub struct Edge<T> {
+    from: T,
+    to: T,
+    #[allow(dead_code)]
+    weight: f64,
+}
+
+#[derive(CustomDisplay)]
+#[display("#{0:06x} alpha={1:?}")]
+pub struct Color<T, A>(T, A);
+
+#[derive(CustomDisplay)]
+#[display("{0}")]
+pub struct Tagged<T, M>(T, PhantomData<M>);
+
+#[derive(CustomDisplay)]
+pub enum Event<K> {
+    #[display("key {key} pressed {count} times")]
+    Key { key: K, count: u32 },
+    #[display("moved to ({0}, {1})")]
+    Move(i32, i32),
+    Quit,
+}
+
+#[derive(CustomDisplay)]
+#[display("{type}: {r#ref}")]
+pub struct Raw {
+    r#type: &'static str,
+    r#ref: u8,
+}
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDisplay)]
+#[display(bound = "T::Value: Display")]
+#[display("value is {value}")]
+pub struct Wrapper<T: Trait> {
+    value: Inner<T>,
+}
+
+#[derive(CustomDisplay)]
+#[display("{inner}")]
+pub struct Inner<T: Trait> {
+    inner: T::Value,
+}
+
+fn assert_display<F: Display>() {}
+
+fn main() {
+    struct NotDisplay;
+
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    let raw = Raw { r#type: "u8", r#ref: 1 };
+    assert_eq!(raw.to_string(), "u8: 1");
+
+    let edge = Edge { from: "a", to: "b", weight: 1.0 };
+    assert_eq!(edge.to_string(), "a -> b");
+
+    // Needs `T: LowerHex` and `A: Debug` rather than `Display`.
+    let color = Color(0xff8800u32, Some(0.5));
+    assert_eq!(color.to_string(), "#ff8800 alpha=Some(0.5)");
+
+    assert_display::<Tagged<u8, NotDisplay>>();
+
+    assert_eq!(Event::Key { key: 'q', count: 2 }.to_string(), "key q pressed 2 times");
+    assert_eq!(Event::<char>::Move(-1, 3).to_string(), "moved to (-1, 3)");
+    assert_eq!(Event::<char>::Quit.to_string(), "Quit");
+
+    assert_display::<Wrapper<Id>>();
+    let wrapper = Wrapper::<Id> { value: Inner { inner: 7 } };
+    assert_eq!(wrapper.to_string(), "value is 7");
+}
@@ -0,0 +1,57 @@
+// For newtypes such as `struct UserId(u64)` it is often more useful to print
+// just the wrapped value, `42`, than `UserId(42)`. The container attribute
+// `#[debug(transparent)]` delegates to the single non-skipped field, including
+// its custom format if it has one, and passes the formatter through unchanged
+// so that width, precision and `{:#?}` still apply.
+//
+// Tuple structs without the attribute print through `debug_tuple`, the same as
+// the standard library derive.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct UserId(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Mask {
+    #[debug = "0b{:08b}"]
+    bits: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Cached {
+    value: Vec<u8>,
+    #[debug(skip)]
+    #[allow(dead_code)]
+    hits: usize,
+}
+
+#[derive(CustomDebug)]
+pub struct Pair(u8, #[debug = "{:x}"] u8);
+
+#[derive(CustomDebug)]
+pub struct User {
+    id: UserId,
+    mask: Mask,
+}
+
+fn main() {
+    assert_eq!(format!("{:?}", UserId(42)), "42");
+    assert_eq!(format!("{:>4?}", UserId(42)), "  42");
+    assert_eq!(format!("{:?}", Mask { bits: 5 }), "0b00000101");
+
+    let cached = Cached { value: vec![1, 2], hits: 3 };
+    assert_eq!(format!("{:?}", cached), "[1, 2]");
+    assert_eq!(format!("{:#?}", cached), "[\n    1,\n    2,\n]");
+
+    assert_eq!(format!("{:?}", Pair(1, 255)), "Pair(1, ff)");
+
+    let user = User {
+        id: UserId(7),
+        mask: Mask { bits: 1 },
+    };
+    assert_eq!(format!("{:?}", user), "User { id: 7, mask: 0b00000001 }");
+}
@@ -0,0 +1,56 @@
+// Printing a multi-megabyte buffer in a Debug log line is a hazard, so fields
+// holding collections can be summarized:
+//
+//   - `#[debug(truncate = N)]` shows the first N elements, then `... (M more)`;
+//   - `#[debug(hex)]` shows a collection of bytes as a hex string, and may be
+//     combined with `truncate`;
+//   - `#[debug(len_only)]` shows only the number of elements.
+//
+// These are implemented with wrapper types around a reference to the field, so
+// they work for any field whose reference implements IntoIterator: vectors,
+// slices, arrays, maps, sets and so on. Fields that are themselves references,
+// such as `&'a [u8]`, are looked through.
+
+use derive_debug::CustomDebug;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Packet<'a, T> {
+    #[debug(truncate = 3)]
+    samples: Vec<T>,
+    #[debug(hex)]
+    header: [u8; 4],
+    #[debug(hex, truncate = 2)]
+    payload: &'a [u8],
+    #[debug(len_only)]
+    index: BTreeMap<String, NotDebug>,
+    #[debug(truncate = 8)]
+    short: Vec<u8>,
+}
+
+pub struct NotDebug;
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug::<Packet<u8>>();
+
+    let payload = vec![0xca, 0xfe, 0xba, 0xbe];
+    let mut index = BTreeMap::new();
+    index.insert("a".to_owned(), NotDebug);
+    index.insert("b".to_owned(), NotDebug);
+
+    let packet = Packet {
+        samples: vec![1, 2, 3, 4, 5],
+        header: [0xde, 0xad, 0xbe, 0xef],
+        payload: &payload,
+        index,
+        short: vec![1, 2],
+    };
+
+    assert_eq!(
+        format!("{:?}", packet),
+        "Packet { samples: [1, 2, 3, ... (2 more)], header: deadbeef, payload: cafe... (2 more), index: len=2, short: [1, 2] }",
+    );
+}
@@ -0,0 +1,64 @@
+// Does the generated code still work if the names it relies on mean something
+// different in the caller's code? This mirrors the builder's test of the same
+// name, shadowing the formatting types as well as the prelude ones.
+//
+// All expanded code refers to items through absolute `::core` paths rather
+// than `std`, which also keeps the derives usable from `#![no_std]` crates.
+// The local `std` module below stands in for such a crate: any `std::...` path
+// in the expansion would resolve to it and fail.
+
+#![allow(dead_code, non_camel_case_types)]
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+mod std {}
+
+type Debug = ();
+type Display = ();
+type Formatter = ();
+type Result = ();
+type Option = ();
+type Some = ();
+type None = ();
+type Ok = ();
+type Fn = ();
+type Sized = ();
+type IntoIterator = ();
+type Iterator = ();
+type usize = ();
+type u8 = ();
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub struct Everything<'a, T> {
+    plain: T,
+    #[debug = "{:?}!"]
+    formatted: T,
+    #[debug(truncate = 2)]
+    truncated: &'a [T],
+    #[debug(hex, truncate = 4)]
+    bytes: &'a [core::primitive::u8],
+    #[debug(len_only)]
+    len: &'a [T],
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Id(core::primitive::u32);
+
+#[derive(CustomDisplay)]
+#[display("{0:x} {1}")]
+pub struct Pair<T>(T, Id);
+
+#[derive(CustomDisplay)]
+pub enum State {
+    Idle,
+}
+
+impl core::fmt::Display for Id {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+fn main() {}
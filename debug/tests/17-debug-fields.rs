@@ -0,0 +1,70 @@
+// Alongside `CustomDebug`, `#[derive(DebugFields)]` implements the DebugFields
+// trait from the debug_fields support crate:
+//
+//     pub trait DebugFields {
+//         fn visit_fields(&self, visitor: &mut dyn FieldVisitor);
+//     }
+//
+// The visitor receives the name of each field together with its value, after
+// any custom format or summary from `#[debug]` has been applied and with
+// skipped fields left out. Structured logging backends can then record the
+// fields individually rather than parsing the Debug output.
+
+use debug_fields::{DebugFields, FieldVisitor};
+use derive_debug::{CustomDebug, DebugFields};
+use std::fmt::Debug;
+
+#[derive(CustomDebug, DebugFields)]
+pub struct Request<T> {
+    method: &'static str,
+    #[debug = "0x{:04x}"]
+    id: u16,
+    #[debug(truncate = 2)]
+    body: Vec<T>,
+    #[debug(skip)]
+    #[allow(dead_code)]
+    token: String,
+}
+
+#[derive(DebugFields)]
+pub struct Point(i32, i32);
+
+/// Records fields as `name=value` pairs, as a JSON or tracing backend might.
+#[derive(Default)]
+struct Recorder {
+    fields: Vec<(String, String)>,
+}
+
+impl FieldVisitor for Recorder {
+    fn visit_field(&mut self, name: &str, value: &dyn Debug) {
+        self.fields.push((name.to_owned(), format!("{:?}", value)));
+    }
+}
+
+fn record(value: &dyn DebugFields) -> Vec<(String, String)> {
+    let mut recorder = Recorder::default();
+    value.visit_fields(&mut recorder);
+    recorder.fields
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn main() {
+    let request = Request {
+        method: "GET",
+        id: 42,
+        body: vec![1u8, 2, 3],
+        token: "secret".to_owned(),
+    };
+    assert_eq!(
+        record(&request),
+        pairs(&[("method", "\"GET\""), ("id", "0x002a"), ("body", "[1, 2, ... (1 more)]")]),
+    );
+
+    assert_eq!(record(&Point(3, -4)), pairs(&[("0", "3"), ("1", "-4")]));
+}
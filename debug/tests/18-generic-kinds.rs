@@ -0,0 +1,82 @@
+// Bound inference only ever bounds type parameters, but it has to look through
+// every kind of generic parameter a struct may declare:
+//
+//   - lifetimes, as in `&'a T`, need no bound of their own and must not stop
+//     the `T: Debug` bound from being inferred;
+//   - const generics, as in `[T; N]`, need no bound either, and their uses in
+//     array lengths or as generic arguments like `Bytes<N>` are not mistaken
+//     for type parameters;
+//   - defaults and existing where clauses are kept.
+//
+// For the first struct below the generated impl needs to look like:
+//
+//     impl<'a, T, const N: usize> Debug for Window<'a, T, N>
+//     where
+//         T: Debug,
+//     {...}
+
+use derive_debug::{CustomDebug, CustomDisplay, DebugFields};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug, DebugFields)]
+pub struct Window<'a, T, const N: usize> {
+    buf: &'a [T; N],
+}
+
+#[derive(CustomDebug)]
+pub struct Borrowed<'a, 'b: 'a> {
+    first: &'a str,
+    second: &'b [u8],
+}
+
+#[derive(CustomDebug)]
+pub struct Bytes<const N: usize> {
+    data: [u8; N],
+}
+
+#[derive(CustomDebug)]
+pub struct Nested<'a, T: 'a, U, const N: usize, const M: usize = 2>
+where
+    U: Clone,
+{
+    bytes: Bytes<N>,
+    grid: [[&'a T; N]; M],
+    marker: PhantomData<U>,
+}
+
+#[derive(CustomDisplay)]
+#[display("{0} of {1}")]
+pub struct Ratio<'a, T, const N: usize>(&'a T, Bytes<N>);
+
+impl<const N: usize> std::fmt::Display for Bytes<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} bytes", N)
+    }
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    #[derive(Clone)]
+    struct NotDebug;
+
+    assert_debug::<Window<u8, 4>>();
+    assert_debug::<Borrowed>();
+    assert_debug::<Bytes<3>>();
+    assert_debug::<Nested<u8, NotDebug, 1>>();
+
+    let window = Window { buf: &[1, 2, 3] };
+    assert_eq!(format!("{:?}", window), "Window { buf: [1, 2, 3] }");
+
+    let nested = Nested::<u8, NotDebug, 1> {
+        bytes: Bytes { data: [7] },
+        grid: [[&1], [&2]],
+        marker: PhantomData,
+    };
+    assert!(format!("{:?}", nested)
+        .starts_with("Nested { bytes: Bytes { data: [7] }, grid: [[1], [2]], marker: PhantomData<"));
+
+    let ratio = Ratio(&3, Bytes { data: [0; 8] });
+    assert_eq!(ratio.to_string(), "3 of 8 bytes");
+}
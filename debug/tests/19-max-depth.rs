@@ -0,0 +1,57 @@
+// Deeply nested or cyclic structures, such as long linked lists or graphs
+// built from `Rc<RefCell<_>>`, make the derived Debug impl recurse until the
+// stack overflows. The container attribute `#[debug(max_depth = N)]` keeps a
+// thread-local count of how many values of the type are being formatted on
+// the current thread and prints `...` in place of anything nested deeper
+// than N levels.
+//
+// The counter is restored when formatting returns, whether it succeeded or
+// not, so consecutive formats of the same value print the same output. Since
+// it relies on `thread_local!`, the attribute needs `std`.
+
+use derive_debug::CustomDebug;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 2)]
+pub struct List {
+    value: u32,
+    next: Option<Box<List>>,
+}
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 3)]
+pub struct Node {
+    name: &'static str,
+    edges: Vec<Rc<RefCell<Node>>>,
+}
+
+fn main() {
+    let list = List {
+        value: 1,
+        next: Some(Box::new(List {
+            value: 2,
+            next: Some(Box::new(List { value: 3, next: None })),
+        })),
+    };
+    let expected = "List { value: 1, next: Some(List { value: 2, next: Some(...) }) }";
+    assert_eq!(format!("{:?}", list), expected);
+    assert_eq!(format!("{:?}", list), expected);
+
+    let short = List { value: 7, next: None };
+    assert_eq!(format!("{:?}", short), "List { value: 7, next: None }");
+
+    // a -> b -> a -> ...
+    let a = Rc::new(RefCell::new(Node { name: "a", edges: Vec::new() }));
+    let b = Rc::new(RefCell::new(Node { name: "b", edges: vec![a.clone()] }));
+    a.borrow_mut().edges.push(b);
+
+    let printed = format!("{:?}", a.borrow());
+    assert!(printed.starts_with("Node { name: \"a\", edges: [RefCell { value: Node { name: \"b\""));
+    assert_eq!(printed.matches("...").count(), 1);
+    assert_eq!(printed.matches("Node {").count(), 3);
+
+    let pretty = format!("{:#?}", a.borrow());
+    assert!(pretty.contains("..."));
+}
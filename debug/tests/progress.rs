@@ -9,4 +9,15 @@ fn tests() {
     t.pass("tests/06-bound-trouble.rs");
     t.pass("tests/07-associated-type.rs");
     t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-attribute-anywhere.rs");
+    t.compile_fail("tests/10-attribute-errors.rs");
+    t.pass("tests/11-precise-bounds.rs");
+    t.pass("tests/12-alternate.rs");
+    t.pass("tests/13-display.rs");
+    t.pass("tests/14-transparent.rs");
+    t.pass("tests/15-summarize.rs");
+    t.pass("tests/16-redefined-prelude-types.rs");
+    t.pass("tests/17-debug-fields.rs");
+    t.pass("tests/18-generic-kinds.rs");
+    t.pass("tests/19-max-depth.rs");
 }
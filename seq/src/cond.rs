@@ -0,0 +1,99 @@
+//! 每次展开时分别判断的 `#if`、`#else`、`#first` 与 `#last`
+//!
+//! ```text
+//! #if N % 2 == 0 { even(N); } #else #if N == 7 { seven(); } #else { odd(N); }
+//! #first { let mut acc = 0; }
+//! #last { acc }
+//! ```
+
+use crate::range::Value;
+use crate::SeqParser;
+
+impl SeqParser {
+    /// `buf[idx]` 为 `#` 时尝试解析条件，返回保留下来的代码与其后第一个 token 的位置
+    pub(crate) fn conditional(
+        &self,
+        buf: &[proc_macro2::TokenTree],
+        idx: usize,
+        values: &[Value],
+        inner: &[proc_macro2::Ident],
+    ) -> syn::Result<Option<(proc_macro2::TokenStream, usize)>> {
+        let keyword = match buf.get(idx + 1) {
+            Some(proc_macro2::TokenTree::Ident(keyword)) => keyword,
+            _ => return Ok(None),
+        };
+        let (nth, count) = self.position.get();
+        let (keep, block, next) = if keyword == "first" || keyword == "last" {
+            let block = match buf.get(idx + 2) {
+                Some(proc_macro2::TokenTree::Group(g)) if g.delimiter() == proc_macro2::Delimiter::Brace => g,
+                _ => return Ok(None),
+            };
+            if !inner.is_empty() {
+                return Ok(None);
+            }
+            let keep = if keyword == "first" { nth == 0 } else { nth + 1 == count };
+            (keep, block, idx + 3)
+        } else if keyword == "if" {
+            let end = match buf[idx + 2..].iter().position(is_brace) {
+                Some(len) => idx + 2 + len,
+                None => return Ok(None),
+            };
+            let condition: proc_macro2::TokenStream = buf[idx + 2..end].iter().cloned().collect();
+            if condition.is_empty() {
+                return Err(syn::Error::new_spanned(keyword, "expected a condition after `#if`"));
+            }
+            let expanded = self.expand_in(&condition, values, inner)?;
+            if crate::has_ident(&expanded) {
+                if !inner.is_empty() {
+                    return Ok(None);
+                }
+                return Err(syn::Error::new_spanned(
+                    condition,
+                    "the condition of `#if` may only use the loop variables and integer literals",
+                ));
+            }
+            let expr: syn::Expr = syn::parse2(expanded)?;
+            let keep = crate::range::eval_condition(&expr)?;
+            match &buf[end] {
+                proc_macro2::TokenTree::Group(block) => (keep, block, end + 1),
+                _ => unreachable!(),
+            }
+        } else {
+            return Ok(None);
+        };
+
+        let mut ret = proc_macro2::TokenStream::new();
+        if keep {
+            ret.extend(self.expand_in(&block.stream(), values, inner)?);
+        }
+
+        //`#if` 之后可接 `#else { ... }` 或 `#else #if ...`
+        let mut next = next;
+        if keyword == "if" {
+            if let (Some(proc_macro2::TokenTree::Punct(p)), Some(proc_macro2::TokenTree::Ident(e))) =
+                (buf.get(next), buf.get(next + 1))
+            {
+                if p.as_char() == '#' && e == "else" {
+                    let (otherwise, after) = match buf.get(next + 2) {
+                        Some(proc_macro2::TokenTree::Group(g)) if g.delimiter() == proc_macro2::Delimiter::Brace => {
+                            let otherwise = if keep { None } else { Some(self.expand_in(&g.stream(), values, inner)?) };
+                            (otherwise, next + 3)
+                        }
+                        Some(proc_macro2::TokenTree::Punct(_)) => match self.conditional(buf, next + 2, values, inner)? {
+                            Some((otherwise, after)) => ((!keep).then_some(otherwise), after),
+                            None => return Err(syn::Error::new_spanned(e, "expected `{` or `#if` after `#else`")),
+                        },
+                        _ => return Err(syn::Error::new_spanned(e, "expected `{` or `#if` after `#else`")),
+                    };
+                    ret.extend(otherwise);
+                    next = after;
+                }
+            }
+        }
+        Ok(Some((ret, next)))
+    }
+}
+
+fn is_brace(t: &proc_macro2::TokenTree) -> bool {
+    matches!(t, proc_macro2::TokenTree::Group(g) if g.delimiter() == proc_macro2::Delimiter::Brace)
+}
@@ -0,0 +1,74 @@
+//! `seq::with_const!`，把常量的值代入其中的 `seq!`
+//!
+//! ```text
+//! macro_rules! with_channels {
+//!     ($($body:tt)*) => {
+//!         seq::with_const! { NUM_CHANNELS = 4; $($body)* }
+//!     };
+//! }
+//! ```
+
+pub(crate) struct WithConst {
+    constants: Vec<(proc_macro2::Ident, proc_macro2::TokenTree)>,
+    body: proc_macro2::TokenStream,
+}
+
+impl syn::parse::Parse for WithConst {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut constants: Vec<(proc_macro2::Ident, proc_macro2::TokenTree)> = Vec::new();
+        loop {
+            let name: proc_macro2::Ident = input.parse()?;
+            if constants.iter().any(|(n, _)| *n == name) {
+                return Err(syn::Error::new(name.span(), format!("duplicate constant `{}`", name)));
+            }
+            input.parse::<syn::Token![=]>()?;
+            let value = match input.parse::<syn::Expr>()? {
+                syn::Expr::Lit(syn::ExprLit { lit, .. }) => {
+                    let mut tokens = quote::quote!(#lit).into_iter();
+                    tokens.next().expect("literal token")
+                }
+                expr => proc_macro2::TokenTree::Group(proc_macro2::Group::new(
+                    proc_macro2::Delimiter::Parenthesis,
+                    quote::quote!(#expr),
+                )),
+            };
+            constants.push((name, value));
+            if input.parse::<Option<syn::Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        input.parse::<syn::Token![;]>()?;
+        Ok(WithConst {
+            constants,
+            body: input.parse()?,
+        })
+    }
+}
+
+impl WithConst {
+    pub(crate) fn expand(&self) -> proc_macro2::TokenStream {
+        self.substitute(&self.body)
+    }
+
+    fn substitute(&self, ts: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        ts.clone()
+            .into_iter()
+            .map(|t| match t {
+                proc_macro2::TokenTree::Group(g) => {
+                    let mut group = proc_macro2::Group::new(g.delimiter(), self.substitute(&g.stream()));
+                    group.set_span(g.span());
+                    proc_macro2::TokenTree::Group(group)
+                }
+                proc_macro2::TokenTree::Ident(i) => match self.constants.iter().find(|(name, _)| *name == i) {
+                    Some((_, value)) => {
+                        let mut value = value.clone();
+                        value.set_span(i.span());
+                        value
+                    }
+                    None => proc_macro2::TokenTree::Ident(i),
+                },
+                t => t,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,85 @@
+//! `#[debug]`，把展开结果写到 target 目录下的 `seq-expand/<file>-<line>-<column>.rs`
+//!
+//! 稳定版的过程宏无法输出提示，借由弃用警告给出文件路径
+
+use std::path::PathBuf;
+
+/// `debug` 为真时写出展开结果，并在其后附上指向文件的警告
+pub(crate) fn write_if(debug: bool, tokens: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    if !debug {
+        return Ok(tokens);
+    }
+
+    let call_site = proc_macro::Span::call_site();
+    let name = format!("{}-{}-{}.rs", source_name(&call_site.file()), call_site.line(), call_site.column());
+    let path = output_dir().join("seq-expand").join(name);
+    let written = std::fs::create_dir_all(path.parent().expect("output directory"))
+        .and_then(|_| std::fs::write(&path, pretty(&tokens)));
+    if let Err(e) = written {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("could not write seq! expansion to {}: {}", path.display(), e),
+        ));
+    }
+
+    let note = format!("seq! expansion written to {}", path.display());
+    let warning = quote::quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct SeqExpansion;
+            let _ = SeqExpansion;
+        };
+    };
+    //表达式的位置放不下条目，放进块中
+    if syn::parse2::<syn::File>(tokens.clone()).is_err() && syn::parse2::<syn::Expr>(tokens.clone()).is_ok() {
+        return Ok(quote::quote!({ #warning #tokens }));
+    }
+    Ok(quote::quote!(#tokens #warning))
+}
+
+/// 源文件相对于 crate 的路径，去掉扩展名并把分隔符换成 `_`
+fn source_name(file: &str) -> String {
+    let file = std::path::Path::new(file);
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let relative = match file.strip_prefix(&manifest_dir) {
+        _ if file.is_relative() => file,
+        Ok(relative) if relative.is_relative() => relative,
+        _ => file.file_name().map(std::path::Path::new).unwrap_or(file),
+    };
+    relative
+        .with_extension("")
+        .to_string_lossy()
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_', "_")
+}
+
+fn output_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("OUT_DIR") {
+        return dir.into();
+    }
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return dir.into();
+    }
+    //工作区成员的 target 目录在工作区根目录，即有 Cargo.lock 的那一层
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let root = manifest_dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.lock").is_file())
+        .unwrap_or(&manifest_dir);
+    root.join("target")
+}
+
+fn pretty(tokens: &proc_macro2::TokenStream) -> String {
+    if let Ok(file) = syn::parse2::<syn::File>(tokens.clone()) {
+        return prettyplease::unparse(&file);
+    }
+    let body = syn::parse::Parser::parse2(syn::Block::parse_within, tokens.clone());
+    match body {
+        Ok(stmts) => prettyplease::unparse(&syn::parse_quote! {
+            fn seq_expansion() {
+                #(#stmts)*
+            }
+        }),
+        //既不是条目也不是语句时，保留未格式化的 token
+        Err(_) => format!("{}\n", tokens),
+    }
+}
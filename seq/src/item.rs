@@ -0,0 +1,40 @@
+//! `#[seq::repeat(...)]`，`seq!` 的属性形式
+//!
+//! ```text
+//! #[seq::repeat(N in 0..4)]
+//! enum Irq {
+//!     Reserved,
+//!     #[each]
+//!     Line__N = N + 1,
+//! }
+//! ```
+
+use crate::SeqParser;
+
+pub(crate) fn expand_item(st: &SeqParser) -> syn::Result<proc_macro2::TokenStream> {
+    if let Ok(mut item) = syn::parse2::<syn::ItemEnum>(st.body.clone()) {
+        if item.variants.iter().any(|v| v.attrs.iter().any(is_each)) {
+            let mut variants = syn::punctuated::Punctuated::new();
+            for mut variant in std::mem::take(&mut item.variants) {
+                let before = variant.attrs.len();
+                variant.attrs.retain(|attr| !is_each(attr));
+                if variant.attrs.len() == before {
+                    variants.push(variant);
+                    continue;
+                }
+                let tokens = quote::quote!(#variant);
+                let iterations = st.iterations();
+                for (nth, values) in iterations.iter().enumerate() {
+                    variants.push(syn::parse2(st.expand_nth(&tokens, values, nth, iterations.len())?)?);
+                }
+            }
+            item.variants = variants;
+            return Ok(quote::quote!(#item));
+        }
+    }
+    st.expand_body()
+}
+
+fn is_each(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("each")
+}
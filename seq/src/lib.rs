@@ -1,53 +1,241 @@
 use proc_macro::TokenStream;
-use syn::Token;
+
+mod cond;
+mod consts;
+mod debug;
+mod item;
+mod options;
+mod paste;
+mod range;
+
 struct SeqParser{
-    variable_ident: syn::Ident,
-    start:i64,
-    end:i64,
+    /// 循环变量，笛卡尔积形式 `(R, C) in (0..4, 0..4)` 时有多个
+    variables: Vec<syn::Ident>,
+    /// 与 `variables` 一一对应的范围
+    ranges:Vec<range::Range>,
+    /// `(I, T) in enumerate [...]`，各范围并行而不是取笛卡尔积
+    zip:bool,
+    /// `#[seq::repeat(...)]` 的形式，标识符用 `name__N` 拼接
+    attribute_form:bool,
+    /// 生成的标识符与字面量在何处解析
+    hygiene:options::Hygiene,
+    /// 正在展开第几次与总共展开几次，供 `#first` 与 `#last` 使用
+    position:std::cell::Cell<(usize,usize)>,
+    /// `#[debug]`，把展开结果写到文件中
+    debug:bool,
     body:proc_macro2::TokenStream,
 }
 
 impl syn::parse::Parse for SeqParser{
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self>{
-        let variable_ident: syn::Ident = input.parse()?;
+        let mut ret = SeqParser::parse_header(input)?;
+
+        let body_buf;
+        syn::braced!(body_buf in input);
+
+        ret.body = body_buf.parse()?;
+        Ok(ret)
+    }
+}
+
+impl SeqParser{
+    /// 解析 `N in 0..4` 部分，`seq!` 与 `#[seq::repeat]` 共用
+    fn parse_header(input: syn::parse::ParseStream) -> syn::Result<Self>{
+        let options: options::Options = input.parse()?;
+        let limit = options.limit;
+
+        let (variables,mut ranges,zip) = if input.peek(syn::token::Paren){
+            let variables_buf;
+            syn::parenthesized!(variables_buf in input);
+            let variables = variables_buf.parse_terminated::<_,syn::Token!(,)>(<syn::Ident as syn::parse::Parse>::parse)?;
+
+            input.parse::<syn::Token!(in)>()?;
+
+            if input.peek(range::enumerate){
+                let enumerate = input.parse::<range::enumerate>()?;
+                if variables.len() != 2{
+                    return Err(syn::Error::new(enumerate.span,"`enumerate` needs two variables, `(I, T)`"));
+                }
+                let list = range::Range::parse(input,limit)?;
+                let indices = range::Range::indices(&list);
+                (variables.into_iter().collect::<Vec<_>>(),vec![indices,list],true)
+            }else{
+                let ranges_buf;
+                let paren = syn::parenthesized!(ranges_buf in input);
+                let mut ranges = Vec::new();
+                while !ranges_buf.is_empty(){
+                    ranges.push(range::Range::parse(&ranges_buf,limit)?);
+                    if ranges_buf.is_empty(){
+                        break;
+                    }
+                    ranges_buf.parse::<syn::Token!(,)>()?;
+                }
+                if ranges.len() != variables.len(){
+                    return Err(syn::Error::new(
+                        paren.span,
+                        format!("expected {} ranges, one for each variable, found {}",variables.len(),ranges.len()),
+                    ));
+                }
+                (variables.into_iter().collect::<Vec<_>>(),ranges,false)
+            }
+        }else{
+            let variable_ident: syn::Ident = input.parse()?;
 
-        input.parse::<syn::Token!(in)>()?;
+            input.parse::<syn::Token!(in)>()?;
 
-        let start: syn::LitInt = input.parse()?;
+            let range = range::Range::parse(input,limit)?;
+            (vec![variable_ident],vec![range],false)
+        };
 
-        input.parse::<Token!(..)>()?;
-        
-        let mut inc = false;
-        if input.peek(syn::Token!(=)){
-            input.parse::<syn::Token!(=)>()?;
-            inc = true;
+        for (idx,variable) in variables.iter().enumerate(){
+            if variables[..idx].contains(variable){
+                return Err(syn::Error::new(variable.span(),format!("duplicate variable `{}`",variable)));
+            }
         }
 
-        let end:syn::LitInt = input.parse()?;
+        for range in ranges.iter_mut(){
+            if let Some(suffix) = &options.suffix{
+                range.format.set_suffix(suffix);
+            }
+            range.check_fits()?;
+            if range.values.is_empty() && !options.allow_empty{
+                return Err(syn::Error::new_spanned(
+                    &range.tokens,
+                    "range is empty, so seq! would expand to nothing; add `#[allow_empty]` if that is intended",
+                ));
+            }
+        }
+        let count = if zip{
+            ranges.iter().map(|r| r.values.len() as i128).min().unwrap_or(0)
+        }else{
+            ranges.iter().fold(1i128,|count,r| count.saturating_mul(r.values.len() as i128))
+        };
+        if count > limit as i128{
+            let tokens = ranges.iter().map(|r| &r.tokens);
+            return Err(options::limit_error(quote::quote!(#(#tokens)*),count,limit));
+        }
 
-        let body_buf;
-        syn::braced!(body_buf in input);
+        Ok(SeqParser{
+            variables,
+            ranges,
+            zip,
+            attribute_form:false,
+            hygiene:options.hygiene,
+            position:std::cell::Cell::new((0,0)),
+            debug:options.debug,
+            body:proc_macro2::TokenStream::new(),
+        })
+    }
 
-        let body: proc_macro2::TokenStream = body_buf.parse()?;
+    /// 有 `#(...)*` 时只重复这些部分，否则重复整个循环体
+    fn expand_body(&self)->syn::Result<proc_macro2::TokenStream>{
+        let buffer = syn::buffer::TokenBuffer::new2(self.body.clone());
 
-        let mut t = SeqParser{
-            variable_ident,
-            start:start.base10_parse()?,
-            end:end.base10_parse()?,
-            body,
+        let (ret_1,expand) = self.find_block_to_expand_and_to_expand(buffer.begin())?;
+        if expand{
+            return Ok(ret_1);
+        }
+
+        let mut ret = proc_macro2::TokenStream::new();
+        let iterations = self.iterations();
+        for (nth,values) in iterations.iter().enumerate(){
+            ret.extend(self.expand_nth(&self.body, values, nth, iterations.len())?);
+        }
+        Ok(ret)
+    }
+
+    /// 每次展开时各变量的取值，按笛卡尔积排列，第一个变量变化最慢
+    fn iterations(&self)->Vec<Vec<range::Value>>{
+        //`enumerate` 的下标与列表并行，不做笛卡尔积
+        if self.zip{
+            let len = self.ranges.iter().map(|r| r.values.len()).min().unwrap_or(0);
+            return (0..len).map(|idx| self.ranges.iter().map(|r| r.values[idx].clone()).collect()).collect();
+        }
+        let mut ret = vec![Vec::new()];
+        for range in self.ranges.iter(){
+            ret = ret.into_iter().flat_map(|prefix:Vec<range::Value>|{
+                range.values.iter().map(move |n|{
+                    let mut values = prefix.clone();
+                    values.push(n.clone());
+                    values
+                })
+            }).collect();
+        }
+        ret
+    }
+
+    /// 生成的 token 位于 `located`，按 `#[hygiene]` 决定其解析位置
+    fn span_for(&self,located:proc_macro2::Span)->proc_macro2::Span{
+        match self.hygiene{
+            options::Hygiene::Input => located,
+            options::Hygiene::CallSite => located.resolved_at(proc_macro2::Span::call_site()),
+            options::Hygiene::MixedSite => located.resolved_at(proc_macro2::Span::mixed_site()),
+        }
+    }
+
+    fn variable_index(&self,ident:&proc_macro2::Ident)->Option<usize>{
+        self.variables.iter().position(|v| v == ident)
+    }
+
+    /// 内层 `seq!` 的变量，不能与外层重名，否则外层的替换会渗入内层
+    fn nested_variables(&self,header:&proc_macro2::Group)->syn::Result<Vec<proc_macro2::Ident>>{
+        let first = header.stream().into_iter().next();
+        let inner_variables = match first{
+            Some(proc_macro2::TokenTree::Ident(i)) => vec![i],
+            Some(proc_macro2::TokenTree::Group(g)) => g.stream().into_iter().filter_map(|t| match t{
+                proc_macro2::TokenTree::Ident(i) => Some(i),
+                _ => None,
+            }).collect(),
+            _ => Vec::new(),
         };
+        for inner in inner_variables.iter(){
+            if self.variable_index(inner).is_some(){
+                return Err(syn::Error::new(
+                    inner.span(),
+                    format!("nested seq! must use a variable distinct from the enclosing `{}`",inner),
+                ));
+            }
+        }
+        Ok(inner_variables)
+    }
+
+    /// 找出表达式中第一个出现的循环变量
+    fn first_variable(&self,ts:&proc_macro2::TokenStream)->Option<usize>{
+        ts.clone().into_iter().find_map(|t| match t{
+            proc_macro2::TokenTree::Ident(i) => self.variable_index(&i),
+            proc_macro2::TokenTree::Group(g) => self.first_variable(&g.stream()),
+            _ => None,
+        })
+    }
 
-        if inc{
-            t.end +=1;
+    /// 求值 `#(...)` 或 `~(...)` 中的表达式，结果沿用其中第一个变量的字面量格式
+    ///
+    /// 不含本层变量或代入后仍有其他标识符的表达式属于内层 `seq!`，原样保留
+    fn eval_in_body(&self,g:&proc_macro2::Group,values:&[range::Value])->syn::Result<Option<(i128,&range::LitFormat)>>{
+        let var_idx = match self.first_variable(&g.stream()){
+            Some(var_idx) => var_idx,
+            None => return Ok(None),
+        };
+        let expanded = self.expand(&g.stream(),values)?;
+        if has_ident(&expanded){
+            return Ok(None);
         }
+        let expr:syn::Expr = syn::parse2(expanded)?;
+        Ok(Some((range::eval(&expr)?,&self.ranges[var_idx].format)))
+    }
 
-        Ok(t)
+    /// 展开总共 `count` 次中的第 `nth` 次
+    fn expand_nth(&self,ts:&proc_macro2::TokenStream,values:&[range::Value],nth:usize,count:usize)-> syn::Result<proc_macro2::TokenStream>{
+        self.position.set((nth,count));
+        self.expand(ts,values)
     }
 
-}
+    fn expand(&self,ts:&proc_macro2::TokenStream,values:&[range::Value])-> syn::Result<proc_macro2::TokenStream>{
+        self.expand_in(ts,values,&[])
+    }
 
-impl SeqParser{
-    fn expand(&self,ts:&proc_macro2::TokenStream,n:i64)-> proc_macro2::TokenStream{
+    /// `inner` 为所在的内层 seq! 的变量
+    fn expand_in(&self,ts:&proc_macro2::TokenStream,values:&[range::Value],inner:&[proc_macro2::Ident])-> syn::Result<proc_macro2::TokenStream>{
         let buf = ts.clone().into_iter().collect::<Vec<_>>();
 
         let mut ret = proc_macro2::TokenStream::new();
@@ -57,38 +245,85 @@ impl SeqParser{
             let tree_node = &buf[idx];
             match tree_node{
                 proc_macro2::TokenTree::Group(g)=>{
-                    let new_stream = self.expand(&g.stream(), n);
-                    let wrap_in_group = proc_macro2::Group::new(g.delimiter(),new_stream);
+                    let new_stream = self.expand_in(&g.stream(), values, inner)?;
+                    let wrap_in_group = with_span(proc_macro2::Group::new(g.delimiter(),new_stream),g.span());
                     ret.extend(quote::quote! (#wrap_in_group));
                 }
                 proc_macro2::TokenTree::Ident(prefix)=>{
-                    if idx + 2 < buf.len(){
-                        if let proc_macro2::TokenTree::Punct(p) = &buf[idx+1]{
-                            if p.as_char() == '~'{
-                                if let proc_macro2::TokenTree::Ident(i) = &buf[idx+2]{
-                                    if i == &self.variable_ident 
-                                        && prefix.span().end() == p.span().start() 
-                                        && p.span().end() == i.span().start()
-                                        {
-                                            let new_ident_litral = format!("{}{}",prefix.to_string(),n);
-                                            let new_ident = proc_macro2::Ident::new(&new_ident_litral.as_str(),prefix.span());
-                                            ret.extend(quote::quote! {#new_ident});
-                                            idx+=3;
-                                            continue;
-                                        }
-                                }
-                            }
+                    //内层 seq! 的变量交给内层去拼接
+                    if let Some(header) = nested_seq_header(&buf[idx..]){
+                        let mut nested = inner.to_vec();
+                        nested.extend(self.nested_variables(header)?);
+                        let bang = &buf[idx+1];
+                        let new_stream = self.expand_in(&header.stream(), values, &nested)?;
+                        let wrap_in_group = with_span(proc_macro2::Group::new(header.delimiter(),new_stream),header.span());
+                        ret.extend(quote::quote! (#prefix #bang #wrap_in_group));
+                        idx += 3;
+                        continue;
+                    }
+
+                    if self.attribute_form{
+                        if let Some(new_ident) = self.paste_underscored(prefix,values)?{
+                            ret.extend(quote::quote! {#new_ident});
+                            idx += 1;
+                            continue;
                         }
                     }
 
-                    if prefix == &self.variable_ident{
-                        let new_ident = proc_macro2::Literal::u64_unsuffixed(n as u64);
+                    if let Some((new_ident,next)) = self.paste(&buf,idx,values,inner)?{
                         ret.extend(quote::quote! {#new_ident});
+                        idx = next;
+                        continue;
+                    }
+
+                    if let Some(var_idx) = self.variable_index(prefix){
+                        match &values[var_idx]{
+                            range::Value::Int(n) => {
+                                let new_literal = self.ranges[var_idx].format.literal(*n,self.span_for(prefix.span()),is_dot(buf.get(idx+1)));
+                                ret.extend(quote::quote! {#new_literal});
+                            }
+                            range::Value::Item(item) => ret.extend(item.clone()),
+                        }
                         idx +=1;
                         continue;
                     }
                     ret.extend(quote::quote! {#tree_node});
                 }
+                //`#(N * 8)` 与 `~(N + 1)` 求值为字面量，`#(...)*` 则留给内层 seq!
+                proc_macro2::TokenTree::Punct(p) if matches!(p.as_char(),'#' | '~') => {
+                    if p.as_char() == '#'{
+                        if let Some((kept,next)) = self.conditional(&buf,idx,values,inner)?{
+                            ret.extend(kept);
+                            idx = next;
+                            continue;
+                        }
+                    }
+                    if let Some(proc_macro2::TokenTree::Group(g)) = buf.get(idx+1){
+                        let is_section = match (buf.get(idx+2),buf.get(idx+3)){
+                            (Some(proc_macro2::TokenTree::Punct(star)),_) if star.as_char() == '*' => touching(g.span(),star.span()),
+                            (Some(proc_macro2::TokenTree::Punct(sep)),Some(proc_macro2::TokenTree::Punct(star))) => {
+                                star.as_char() == '*' && touching(g.span(),sep.span()) && touching(sep.span(),star.span())
+                            }
+                            _ => false,
+                        };
+                        if g.delimiter() == proc_macro2::Delimiter::Parenthesis && !(p.as_char() == '#' && is_section){
+                            if let Some((n,format)) = self.eval_in_body(g,values)?{
+                                format.check_fits(n,g)?;
+                                let new_literal = format.literal(n,self.span_for(joined(p.span(),g.span())),is_dot(buf.get(idx+2)));
+                                ret.extend(quote::quote! {#new_literal});
+                                idx += 2;
+                                continue;
+                            }
+                        }
+                    }
+                    ret.extend(quote::quote! (#tree_node));
+                }
+                proc_macro2::TokenTree::Literal(lit) => {
+                    match self.interpolate(lit,values){
+                        Some(new_lit) => ret.extend(quote::quote! (#new_lit)),
+                        None => ret.extend(quote::quote! (#tree_node)),
+                    }
+                }
                 _ =>{
                     ret.extend(quote::quote! (#tree_node));
                 }
@@ -96,50 +331,68 @@ impl SeqParser{
 
             idx+=1;
         }
-        ret
+        Ok(ret)
     }
 
-    fn find_block_to_expand_and_to_expand(&self,c:syn::buffer::Cursor)->(proc_macro2::TokenStream,bool){
+    fn find_block_to_expand_and_to_expand(&self,c:syn::buffer::Cursor)->syn::Result<(proc_macro2::TokenStream,bool)>{
         let mut found = false;
         let mut ret = proc_macro2::TokenStream::new();
 
         let mut cursor = c;
 
         while !cursor.eof(){
+            //内层 seq! 中的 `#(...)*` 属于内层，原样保留
+            if let Some((ident,cursor_1)) = cursor.ident(){
+                if ident == "seq"{
+                    if let Some((bang,cursor_2)) = cursor_1.punct(){
+                        if bang.as_char() == '!'{
+                            if let Some((proc_macro2::TokenTree::Group(g),cursor_3)) = cursor_2.token_tree(){
+                                self.nested_variables(&g)?;
+                                ret.extend(quote::quote!(#ident #bang #g));
+                                cursor = cursor_3;
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+
             if let Some((punct_prefix,cursor_1)) = cursor.punct(){
                 if punct_prefix.as_char() == '#'{
-                    if let Some((group_cur,_,cursor_2)) = cursor_1.group(proc_macro2::Delimiter::Parenthesis){
-                        if let Some((punct_suffix,cursor_3)) = cursor_2.punct(){
-                            if punct_suffix.as_char() == '*'{
-                                for i in self.start..self.end{
-                                    let t = self.expand(&group_cur.token_stream(), i);
-                                    ret.extend(t);
+                    if let Some((group_cur,group_span,cursor_2)) = cursor_1.group(proc_macro2::Delimiter::Parenthesis){
+                        if let Some((separator,cursor_3)) = section_end(group_span,cursor_2){
+                            let iterations = self.iterations();
+                            for (i,values) in iterations.iter().enumerate(){
+                                if i > 0{
+                                    ret.extend(separator.clone());
                                 }
-                                cursor = cursor_3;
-                                found = true;
-                                continue;
+                                let t = self.expand_nth(&group_cur.token_stream(), values, i, iterations.len())?;
+                                ret.extend(t);
                             }
+                            cursor = cursor_3;
+                            found = true;
+                            continue;
                         }
                     }
                 }
             }
 
-            if let Some((group_cur,_,next_cur)) = cursor.group(proc_macro2::Delimiter::Brace){
-                let (t,f) = self.find_block_to_expand_and_to_expand(group_cur);
-                found = f;
-                ret.extend(quote::quote!( {#t} ));
+            if let Some((group_cur,span,next_cur)) = cursor.group(proc_macro2::Delimiter::Brace){
+                let (t,f) = self.find_block_to_expand_and_to_expand(group_cur)?;
+                found |= f;
+                ret.extend([with_span(proc_macro2::Group::new(proc_macro2::Delimiter::Brace,t),span)]);
                 cursor = next_cur;
                 continue;
-            }else if let Some((group_cur,_,next_cur)) = cursor.group(proc_macro2::Delimiter::Bracket){
-                let (t,f) = self.find_block_to_expand_and_to_expand(group_cur);
-                found = f;
-                ret.extend(quote::quote!( [#t] ));
+            }else if let Some((group_cur,span,next_cur)) = cursor.group(proc_macro2::Delimiter::Bracket){
+                let (t,f) = self.find_block_to_expand_and_to_expand(group_cur)?;
+                found |= f;
+                ret.extend([with_span(proc_macro2::Group::new(proc_macro2::Delimiter::Bracket,t),span)]);
                 cursor = next_cur;
                 continue;
-            }else if let Some((group_cur,_,next_cur)) = cursor.group(proc_macro2::Delimiter::Parenthesis) {
-                let (t,f) = self.find_block_to_expand_and_to_expand(group_cur);
-                found = f;
-                ret.extend(quote::quote!( (#t) ));
+            }else if let Some((group_cur,span,next_cur)) = cursor.group(proc_macro2::Delimiter::Parenthesis) {
+                let (t,f) = self.find_block_to_expand_and_to_expand(group_cur)?;
+                found |= f;
+                ret.extend([with_span(proc_macro2::Group::new(proc_macro2::Delimiter::Parenthesis,t),span)]);
                 cursor = next_cur;
                 continue;
             }else if let Some((punct,next_cur)) = cursor.punct() {
@@ -160,31 +413,108 @@ impl SeqParser{
                 continue;
             }
         }
-        return (ret,found);
+        Ok((ret,found))
+    }
+}
+
+/// 重建的分组沿用原来的位置，否则错误会指向整个 seq! 调用
+fn with_span(mut group:proc_macro2::Group,span:proc_macro2::Span)->proc_macro2::TokenTree{
+    group.set_span(span);
+    proc_macro2::TokenTree::Group(group)
+}
+
+/// 覆盖 `first` 到 `last` 的位置
+///
+/// 只有 nightly 编译器能合并位置，其余情况下为 `first` 的位置
+fn joined(first:proc_macro2::Span,last:proc_macro2::Span)->proc_macro2::Span{
+    first.join(last).unwrap_or(first)
+}
+
+/// `#(...)` 之后紧贴着的 `*`、`,*` 或 `;*` 等，返回分隔符与其后的位置
+///
+/// `*` 须紧贴括号或分隔符，`#(N * 8) * 2` 仍是求值后乘以二
+fn section_end(group:proc_macro2::Span,cursor:syn::buffer::Cursor)->Option<(Option<proc_macro2::Punct>,syn::buffer::Cursor)>{
+    let (punct,next) = cursor.punct()?;
+    if !touching(group,punct.span()){
+        return None;
+    }
+    if punct.as_char() == '*'{
+        return Some((None,next));
+    }
+    match next.punct(){
+        Some((star,next)) if star.as_char() == '*' && touching(punct.span(),star.span()) => Some((Some(punct),next)),
+        _ => None,
+    }
+}
+
+/// 方法调用或字段访问的 `.`，负数字面量在其前需要括号
+fn is_dot(t:Option<&proc_macro2::TokenTree>)->bool{
+    matches!(t,Some(proc_macro2::TokenTree::Punct(p)) if p.as_char() == '.')
+}
+
+/// `right` 紧接在 `left` 之后
+///
+/// 其他过程宏生成的 token 通常共用一个位置，此时视为紧接
+fn touching(left:proc_macro2::Span,right:proc_macro2::Span)->bool{
+    left.end() == right.start() || (left.start() == right.start() && left.end() == right.end())
+}
+
+fn has_ident(ts:&proc_macro2::TokenStream)->bool{
+    ts.clone().into_iter().any(|t| match t{
+        proc_macro2::TokenTree::Ident(_) => true,
+        proc_macro2::TokenTree::Group(g) => has_ident(&g.stream()),
+        _ => false,
+    })
+}
+
+/// `seq! ...` 或 `seq::seq! ...` 时返回其参数所在的分组
+fn nested_seq_header(buf:&[proc_macro2::TokenTree])->Option<&proc_macro2::Group>{
+    match buf{
+        [proc_macro2::TokenTree::Ident(i),proc_macro2::TokenTree::Punct(bang),proc_macro2::TokenTree::Group(g),..]
+            if i == "seq" && bang.as_char() == '!' => Some(g),
+        _ => None,
     }
 }
 
 #[proc_macro]
 pub fn seq(input: TokenStream) -> TokenStream {
     let st = syn::parse_macro_input!(input as SeqParser);
-    // eprintln!("st.variable_ient:{}",st.variable_ident.to_string());
-    // eprintln!("st.body:{}",st.body);
 
-    let mut ret = proc_macro2::TokenStream::new();
-
-    let buffer = syn::buffer::TokenBuffer::new2(st.body.clone());
+    let ret = match st.expand_body().and_then(|t| debug::write_if(st.debug,t)){
+        Ok(t) => t,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    ret.into()
+}
 
-    let (ret_1,expand) = st.find_block_to_expand_and_to_expand(buffer.begin());
-    if expand{
-        return ret_1.into();
-    }
+/// `seq!` 的属性形式，重复所修饰的条目
+///
+/// ```text
+/// #[seq::repeat(N in 0..4)]
+/// fn handler__N() -> u32 { N }
+/// ```
+#[proc_macro_attribute]
+pub fn repeat(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut st = match syn::parse::Parser::parse(SeqParser::parse_header,args){
+        Ok(st) => st,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    st.attribute_form = true;
+    st.body = input.into();
 
-    for i in st.start..st.end{
-        ret.extend(st.expand(&st.body, i));
+    match item::expand_item(&st).and_then(|t| debug::write_if(st.debug,t)){
+        Ok(t) => t.into(),
+        Err(e) => e.to_compile_error().into(),
     }
+}
 
-
-    // eprintln!("TokenStream:\n{}",ret);
-    return ret.into();
-    // proc_macro::TokenStream::new()
+/// 把常量的值代入其中的 `seq!`
+///
+/// ```text
+/// seq::with_const! { NUM_CHANNELS = 4; seq!(N in 0..NUM_CHANNELS { ... }); }
+/// ```
+#[proc_macro]
+pub fn with_const(input: TokenStream) -> TokenStream {
+    let st = syn::parse_macro_input!(input as consts::WithConst);
+    st.expand().into()
 }
@@ -0,0 +1,117 @@
+//! `seq!` 开头以属性形式给出的选项
+//!
+//! ```text
+//! seq!(#[allow_empty] #[limit = 100000] N in 0..LEN { ... })
+//! seq!(#[hygiene = "mixed_site"] N in 0..4 { ... })
+//! seq!(#[suffix = usize] N in 0..4 { ... })
+//! seq!(#[debug] N in 0..4 { ... })
+//! ```
+
+/// 默认的展开次数上限
+pub(crate) const DEFAULT_LIMIT: usize = 16384;
+
+#[derive(Clone, Copy)]
+pub(crate) enum Hygiene {
+    /// 沿用写下的 token 的解析位置
+    Input,
+    CallSite,
+    MixedSite,
+}
+
+pub(crate) struct Options {
+    /// `#[allow_empty]`
+    pub allow_empty: bool,
+    /// `#[limit = N]`
+    pub limit: usize,
+    /// `#[hygiene = "..."]`
+    pub hygiene: Hygiene,
+    /// `#[suffix = usize]`
+    pub suffix: Option<syn::Ident>,
+    /// `#[debug]`
+    pub debug: bool,
+}
+
+impl syn::parse::Parse for Options {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut allow_empty = None;
+        let mut limit = None;
+        let mut hygiene = None;
+        let mut suffix = None;
+        let mut debug = None;
+        for attr in input.call(syn::Attribute::parse_outer)? {
+            //类型不是字面量，`parse_meta` 无法解析
+            if attr.path.is_ident("suffix") {
+                let parser = |input: syn::parse::ParseStream| {
+                    input.parse::<syn::Token![=]>()?;
+                    input.parse::<syn::Ident>()
+                };
+                let ty = syn::parse::Parser::parse2(parser, attr.tokens.clone());
+                let ty = match ty {
+                    Ok(ty) if crate::range::integer_bounds(&ty.to_string()).is_some() => ty,
+                    _ => return Err(syn::Error::new_spanned(&attr, "expected `#[suffix = T]` with an integer type such as `usize`")),
+                };
+                if suffix.is_some() {
+                    return Err(syn::Error::new_spanned(attr, "duplicate seq! option"));
+                }
+                suffix = Some(ty);
+                continue;
+            }
+            let meta = attr.parse_meta()?;
+            let key = meta.path().get_ident().map(|i| i.to_string()).unwrap_or_default();
+            match (key.as_str(), &meta) {
+                ("allow_empty", syn::Meta::Path(_)) => set_once(&mut allow_empty, true, &meta)?,
+                ("limit", syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Int(n), .. })) => {
+                    set_once(&mut limit, n.base10_parse()?, &meta)?;
+                }
+                ("hygiene", syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. })) => {
+                    let value = match s.value().as_str() {
+                        "call_site" => Hygiene::CallSite,
+                        "mixed_site" => Hygiene::MixedSite,
+                        _ => return Err(syn::Error::new_spanned(s, "expected \"call_site\" or \"mixed_site\"")),
+                    };
+                    set_once(&mut hygiene, value, &meta)?;
+                }
+                ("debug", syn::Meta::Path(_)) => set_once(&mut debug, true, &meta)?,
+                ("allow_empty", _) => return Err(syn::Error::new_spanned(meta, "expected `#[allow_empty]`")),
+                ("debug", _) => return Err(syn::Error::new_spanned(meta, "expected `#[debug]`")),
+                ("limit", _) => return Err(syn::Error::new_spanned(meta, "expected `#[limit = N]`")),
+                ("hygiene", _) => {
+                    return Err(syn::Error::new_spanned(meta, "expected `#[hygiene = \"call_site\"]` or `\"mixed_site\"`"));
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        meta.path(),
+                        "unknown seq! option, expected one of: `allow_empty`, `limit`, `hygiene`, `suffix`, `debug`",
+                    ))
+                }
+            }
+        }
+        Ok(Options {
+            allow_empty: allow_empty.unwrap_or(false),
+            limit: limit.unwrap_or(DEFAULT_LIMIT),
+            hygiene: hygiene.unwrap_or(Hygiene::Input),
+            suffix,
+            debug: debug.unwrap_or(false),
+        })
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, meta: &syn::Meta) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(meta, "duplicate seq! option"));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// 展开次数超过上限时的错误，指向头部的范围
+pub(crate) fn limit_error(tokens: impl quote::ToTokens, count: i128, limit: usize) -> syn::Error {
+    syn::Error::new_spanned(
+        tokens,
+        format!(
+            "seq! would expand its body {} times, more than the limit of {}; \
+             raise it with `#[limit = N]` before the loop variable",
+            count, limit
+        ),
+    )
+}
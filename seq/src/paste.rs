@@ -0,0 +1,351 @@
+//! 把循环变量拼接进标识符与字符串字面量
+//!
+//! ```text
+//! Variant~N         Variant3
+//! f~N~_handler      f3_handler
+//! Field~(N + 1)     Field4
+//! Irq~N:02          Irq03
+//! Reg:upper~_~N:02  REG_03
+//! my_reg:pascal~N   MyReg3
+//! read_reg:camel~N  readReg3
+//! "reg~{N}h"        "reg3h"
+//! ```
+
+use crate::range::Value;
+use crate::SeqParser;
+
+impl SeqParser {
+    /// 从 `buf[idx]` 开始拼接标识符，返回拼出的标识符与其后第一个 token 的位置
+    ///
+    /// 不构成拼接时返回 `None`，遇到内层 `seq!` 的变量时在其前结束
+    pub(crate) fn paste(
+        &self,
+        buf: &[proc_macro2::TokenTree],
+        idx: usize,
+        values: &[Value],
+        inner: &[proc_macro2::Ident],
+    ) -> syn::Result<Option<(proc_macro2::Ident, usize)>> {
+        let prefix = match &buf[idx] {
+            proc_macro2::TokenTree::Ident(prefix) => prefix,
+            _ => return Ok(None),
+        };
+
+        let mut ret = String::new();
+        let mut pos = idx;
+        let mut pasted = false;
+        //只有确实构成拼接时才报告的错误
+        let mut deferred: Option<syn::Error> = None;
+        loop {
+            let mut segment = match &buf[pos] {
+                proc_macro2::TokenTree::Ident(i) => match self.variable_index(i).map(|var_idx| &values[var_idx]) {
+                    Some(Value::Int(n)) => Segment::Number(*n),
+                    // `read_~T` 对类型列表中的 `u8` 得到 `read_u8`
+                    Some(Value::Item(item)) => match syn::parse2::<proc_macro2::Ident>(item.clone()) {
+                        Ok(ident) => Segment::Text(ident.to_string()),
+                        Err(_) => {
+                            deferred.get_or_insert(syn::Error::new(
+                                i.span(),
+                                format!("cannot paste `{}` into an identifier", item),
+                            ));
+                            Segment::Text(String::new())
+                        }
+                    },
+                    None => Segment::Text(i.to_string()),
+                },
+                proc_macro2::TokenTree::Group(g) => match self.eval_in_body(g, values)? {
+                    Some((n, _)) => Segment::Number(n),
+                    None => unreachable!("checked by `segment_at`"),
+                },
+                _ => unreachable!("checked by `segment_at`"),
+            };
+            if let Segment::Number(n) = segment {
+                if n < 0 && deferred.is_none() {
+                    deferred = Some(syn::Error::new(
+                        buf[pos].span(),
+                        format!("cannot paste negative value {} into an identifier", n),
+                    ));
+                }
+            }
+            pos += 1;
+
+            while let Some((modifier, span)) = modifier_at(buf, pos) {
+                segment = match segment.apply(&modifier, span) {
+                    Ok(segment) => segment,
+                    Err((segment, err)) => {
+                        deferred.get_or_insert(err);
+                        segment
+                    }
+                };
+                pos += 2;
+            }
+            ret.push_str(&segment.into_string());
+
+            match buf.get(pos) {
+                Some(proc_macro2::TokenTree::Punct(p))
+                    if p.as_char() == '~' && self.segment_at(buf, pos + 1, p, values, inner)? =>
+                {
+                    pos += 1;
+                    pasted = true;
+                }
+                _ => break,
+            }
+        }
+
+        if !pasted {
+            return Ok(None);
+        }
+        if let Some(err) = deferred {
+            return Err(err);
+        }
+        if !is_ident(&ret) {
+            return Err(syn::Error::new(
+                prefix.span(),
+                format!("pasted identifier `{}` is not a valid identifier", ret),
+            ));
+        }
+        let span = self.span_for(crate::joined(prefix.span(), buf[pos - 1].span()));
+        Ok(Some((proc_macro2::Ident::new(&ret, span), pos)))
+    }
+
+    /// `~` 之后能否接上一段：紧贴 `~` 的标识符或可求值的括号表达式
+    fn segment_at(
+        &self,
+        buf: &[proc_macro2::TokenTree],
+        pos: usize,
+        tilde: &proc_macro2::Punct,
+        values: &[Value],
+        inner: &[proc_macro2::Ident],
+    ) -> syn::Result<bool> {
+        let adjacent = |span: proc_macro2::Span| tilde.span().end() == span.start();
+        Ok(match buf.get(pos) {
+            Some(proc_macro2::TokenTree::Ident(i)) => adjacent(i.span()) && !inner.contains(i),
+            Some(proc_macro2::TokenTree::Group(g)) if g.delimiter() == proc_macro2::Delimiter::Parenthesis => {
+                adjacent(g.span()) && self.eval_in_body(g, values)?.is_some()
+            }
+            _ => false,
+        })
+    }
+}
+
+impl SeqParser {
+    /// `#[seq::repeat]` 中的拼接：`handler__N` 得到 `handler0`
+    pub(crate) fn paste_underscored(
+        &self,
+        ident: &proc_macro2::Ident,
+        values: &[Value],
+    ) -> syn::Result<Option<proc_macro2::Ident>> {
+        let text = ident.to_string();
+        let mut parts = text.split("__");
+        let mut ret = parts.next().unwrap_or_default().to_string();
+        let mut pasted = false;
+        for part in parts {
+            match self.variables.iter().position(|v| v == part).map(|var_idx| &values[var_idx]) {
+                Some(Value::Int(n)) if *n >= 0 => ret.push_str(&n.to_string()),
+                Some(Value::Item(item)) if syn::parse2::<proc_macro2::Ident>(item.clone()).is_ok() => {
+                    ret.push_str(&item.to_string());
+                }
+                Some(value) => {
+                    let value = match value {
+                        Value::Int(n) => n.to_string(),
+                        Value::Item(item) => item.to_string(),
+                    };
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("cannot paste `{}` into an identifier", value),
+                    ));
+                }
+                None => {
+                    ret.push_str("__");
+                    ret.push_str(part);
+                    continue;
+                }
+            }
+            pasted = true;
+        }
+
+        if !pasted || ret.is_empty() {
+            return Ok(None);
+        }
+        if !is_ident(&ret) {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("pasted identifier `{}` is not a valid identifier", ret),
+            ));
+        }
+        Ok(Some(proc_macro2::Ident::new(&ret, self.span_for(ident.span()))))
+    }
+
+    /// 替换字符串字面量中的 `~N` 与 `~{N}`，其余字面量原样返回 `None`
+    pub(crate) fn interpolate(&self, lit: &proc_macro2::Literal, values: &[Value]) -> Option<proc_macro2::Literal> {
+        let lit_str = match syn::Lit::new(lit.clone()) {
+            syn::Lit::Str(lit_str) => lit_str,
+            _ => return None,
+        };
+        let value = lit_str.value();
+        if !value.contains('~') {
+            return None;
+        }
+
+        let mut ret = String::with_capacity(value.len());
+        let mut changed = false;
+        let mut rest = value.as_str();
+        while let Some(pos) = rest.find('~') {
+            ret.push_str(&rest[..pos]);
+            rest = &rest[pos + 1..];
+            let (name, after) = match rest.strip_prefix('{').and_then(|r| r.split_once('}')) {
+                Some((name, after)) => (name, after),
+                None => {
+                    let len = rest.find(|c: char| c != '_' && !c.is_alphanumeric()).unwrap_or(rest.len());
+                    (&rest[..len], &rest[len..])
+                }
+            };
+            let var_idx = self.variables.iter().position(|v| v == name);
+            match var_idx.map(|var_idx| &values[var_idx]) {
+                Some(Value::Int(n)) => ret.push_str(&n.to_string()),
+                Some(Value::Item(item)) => ret.push_str(&item.to_string()),
+                None => {
+                    ret.push('~');
+                    continue;
+                }
+            }
+            changed = true;
+            rest = after;
+        }
+        ret.push_str(rest);
+
+        if !changed {
+            return None;
+        }
+        let mut new_lit = proc_macro2::Literal::string(&ret);
+        new_lit.set_span(lit.span());
+        Some(new_lit)
+    }
+}
+
+enum Segment {
+    Number(i128),
+    Text(String),
+}
+
+impl Segment {
+    /// 出错时连同原样的一段返回
+    fn apply(self, modifier: &Modifier, span: proc_macro2::Span) -> Result<Segment, (Segment, syn::Error)> {
+        Ok(match (modifier, self) {
+            (Modifier::Width(width), Segment::Number(n)) => Segment::Text(format!("{:0width$}", n, width = *width)),
+            (Modifier::Width(_), segment) => {
+                return Err((segment, syn::Error::new(span, "zero-padding only applies to the loop variable")));
+            }
+            (Modifier::Case(case), segment) => Segment::Text(case.convert(&segment.into_string())),
+        })
+    }
+
+    fn into_string(self) -> String {
+        match self {
+            Segment::Number(n) => n.to_string(),
+            Segment::Text(text) => text,
+        }
+    }
+}
+
+enum Modifier {
+    /// `:02`
+    Width(usize),
+    /// `:upper` 等
+    Case(Case),
+}
+
+#[derive(Clone, Copy)]
+enum Case {
+    Upper,
+    Lower,
+    Snake,
+    Pascal,
+    Camel,
+}
+
+impl Case {
+    fn convert(self, text: &str) -> String {
+        match self {
+            Case::Upper => text.to_uppercase(),
+            Case::Lower => text.to_lowercase(),
+            // FooBar -> foo_bar
+            Case::Snake => {
+                let mut ret = String::with_capacity(text.len() + 4);
+                for (idx, c) in text.char_indices() {
+                    if c.is_uppercase() && idx > 0 && !ret.ends_with('_') {
+                        ret.push('_');
+                    }
+                    ret.extend(c.to_lowercase());
+                }
+                ret
+            }
+            // foo_bar -> FooBar
+            Case::Pascal => text
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                        None => String::new(),
+                    }
+                })
+                .collect(),
+            // foo_bar -> fooBar
+            Case::Camel => {
+                let pascal = Case::Pascal.convert(text);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+        }
+    }
+}
+
+/// 紧贴在一段之后的 `:02` 或 `:upper`
+fn modifier_at(buf: &[proc_macro2::TokenTree], pos: usize) -> Option<(Modifier, proc_macro2::Span)> {
+    let colon = match (buf.get(pos - 1), buf.get(pos)) {
+        (Some(prev), Some(proc_macro2::TokenTree::Punct(colon)))
+            if colon.as_char() == ':'
+                && colon.spacing() == proc_macro2::Spacing::Alone
+                && prev.span().end() == colon.span().start() =>
+        {
+            colon
+        }
+        _ => return None,
+    };
+    let next = buf.get(pos + 1)?;
+    if colon.span().end() != next.span().start() {
+        return None;
+    }
+    let modifier = match next {
+        // 只接受 `:02` 这样以 0 开头的写法，`value~N:0` 仍是结构体字段初始化
+        proc_macro2::TokenTree::Literal(lit) => {
+            let width = lit.to_string();
+            if width.len() < 2 || !width.starts_with('0') {
+                return None;
+            }
+            Modifier::Width(width.parse().ok()?)
+        }
+        proc_macro2::TokenTree::Ident(i) => Modifier::Case(match i.to_string().as_str() {
+            "upper" => Case::Upper,
+            "lower" => Case::Lower,
+            "snake" => Case::Snake,
+            "pascal" => Case::Pascal,
+            "camel" => Case::Camel,
+            _ => return None,
+        }),
+        _ => return None,
+    };
+    Some((modifier, next.span()))
+}
+
+fn is_ident(text: &str) -> bool {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if first == '_' || first.is_alphabetic() => {}
+        _ => return false,
+    }
+    text != "_" && chars.all(|c| c == '_' || c.is_alphanumeric())
+}
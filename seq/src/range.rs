@@ -0,0 +1,436 @@
+//! `seq!` 头部的范围
+//!
+//! ```text
+//! seq!(N in -3..3 { ... })
+//! seq!(N in 0x00u8..0x10u8 { ... })
+//! seq!(N in 0..(1 << 4) step 4 { ... })
+//! seq!(N in (0..8).rev() { ... })
+//! seq!(T in [u8, u16, u32, u64] { ... })
+//! seq!((I, T) in enumerate [u8, u16] { ... })
+//! ```
+//!
+//! 边界是常量表达式，代入的字面量沿用边界的进制、位数与后缀
+
+use std::str::FromStr;
+
+use syn::Token;
+
+syn::custom_keyword!(step);
+syn::custom_keyword!(rev);
+syn::custom_keyword!(enumerate);
+
+/// 代入循环变量的一个值
+#[derive(Clone)]
+pub(crate) enum Value {
+    Int(i128),
+    /// 列表中的一项，例如类型
+    Item(proc_macro2::TokenStream),
+}
+
+pub(crate) struct Range {
+    /// 依次代入循环变量的值
+    pub values: Vec<Value>,
+    /// 替换循环变量时生成的字面量格式
+    pub format: LitFormat,
+    /// 头部写下的范围，用于错误位置
+    pub tokens: proc_macro2::TokenStream,
+}
+
+impl Range {
+    /// 解析一个范围，数值范围的长度不能超过 `limit`
+    pub(crate) fn parse(input: syn::parse::ParseStream, limit: usize) -> syn::Result<Range> {
+        let begin = input.cursor();
+        let mut ret = parse_range(input, limit)?;
+
+        let mut tokens = proc_macro2::TokenStream::new();
+        let mut cursor = begin;
+        while cursor != input.cursor() {
+            let (tt, next) = cursor.token_tree().expect("parsed tokens");
+            tokens.extend([tt]);
+            cursor = next;
+        }
+        ret.tokens = tokens;
+        Ok(ret)
+    }
+
+    /// 数值都要放得进后缀所表示的整数类型
+    pub(crate) fn check_fits(&self) -> syn::Result<()> {
+        for value in self.values.iter() {
+            if let Value::Int(n) = value {
+                self.format.check_fits(*n, &self.tokens)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `enumerate [...]` 中与列表并行的下标
+    pub(crate) fn indices(list: &Range) -> Range {
+        Range {
+            values: (0..list.values.len() as i128).map(Value::Int).collect(),
+            format: LitFormat::default(),
+            tokens: list.tokens.clone(),
+        }
+    }
+}
+
+fn parse_range(input: syn::parse::ParseStream, limit: usize) -> syn::Result<Range> {
+    let mut ret = if is_rev_call(input) {
+        let content;
+        syn::parenthesized!(content in input);
+        let mut inner = Range::parse(&content, limit)?;
+        input.parse::<Token!(.)>()?;
+        input.parse::<rev>()?;
+        let args;
+        syn::parenthesized!(args in input);
+        if !args.is_empty() {
+            return Err(args.error("`rev()` takes no arguments"));
+        }
+        inner.values.reverse();
+        inner
+    } else if input.peek(syn::token::Bracket) {
+        parse_list(input)?
+    } else {
+        //数值范围自己处理 `step`，以便按步进后的长度检查上限
+        return parse_bounds(input, limit);
+    };
+
+    if let Some(step) = parse_step(input)? {
+        ret.values = ret.values.into_iter().step_by(step).collect();
+    }
+    Ok(ret)
+}
+
+/// 可选的 `step K`
+fn parse_step(input: syn::parse::ParseStream) -> syn::Result<Option<usize>> {
+    if !input.peek(step) {
+        return Ok(None);
+    }
+    let step_token = input.parse::<step>()?;
+    let step = parse_bound(collect_until_body(input)?)?;
+    usize::try_from(step.value)
+        .ok()
+        .filter(|step| *step > 0)
+        .map(Some)
+        .ok_or_else(|| syn::Error::new(step_token.span, "`step` must be a positive integer"))
+}
+
+/// `(...).rev()`，而不是以括号表达式开头的边界 `(...)..end`
+fn is_rev_call(input: syn::parse::ParseStream) -> bool {
+    if !input.peek(syn::token::Paren) {
+        return false;
+    }
+    let fork = input.fork();
+    fork.parse::<proc_macro2::TokenTree>().is_ok()
+        && fork.peek(Token!(.))
+        && !fork.peek(Token!(..))
+        && fork.parse::<Token!(.)>().is_ok()
+        && fork.peek(rev)
+}
+
+fn parse_bounds(input: syn::parse::ParseStream, limit: usize) -> syn::Result<Range> {
+    let mut start_tokens = proc_macro2::TokenStream::new();
+    while !input.is_empty() && !input.peek(Token!(..)) {
+        start_tokens.extend([input.parse::<proc_macro2::TokenTree>()?]);
+    }
+    let start = parse_bound(start_tokens.clone())?;
+
+    let inclusive = if input.peek(Token!(..=)) {
+        input.parse::<Token!(..=)>()?;
+        true
+    } else {
+        input.parse::<Token!(..)>()?;
+        false
+    };
+
+    let end_tokens = collect_until_body(input)?;
+    let end = parse_bound(end_tokens.clone())?;
+    let step = parse_step(input)?.unwrap_or(1);
+    let end_value = match inclusive {
+        true => end.value.checked_add(1).ok_or_else(|| {
+            syn::Error::new_spanned(&end_tokens, format!("inclusive range cannot end at {}, the largest value seq! supports", end.value))
+        })?,
+        false => end.value,
+    };
+    if start.value > end_value {
+        return Err(syn::Error::new_spanned(
+            quote::quote!(#start_tokens #end_tokens),
+            format!(
+                "range starts at {} but ends before it, at {}; write `({}..={}).rev()` to count down",
+                start.value,
+                end.value,
+                //同样从 start 数起，不含 end 时最后一个值是 end + 1
+                if inclusive { end.value } else { end.value + 1 },
+                start.value
+            ),
+        ));
+    }
+
+    //在生成取值之前按步进后的个数检查，避免 `0..i64::MAX` 耗尽内存
+    let len = end_value.checked_sub(start.value).ok_or_else(|| {
+        syn::Error::new_spanned(
+            quote::quote!(#start_tokens #end_tokens),
+            format!("range from {} to {} is too long to expand", start.value, end.value),
+        )
+    })?;
+    let step_i128 = step as i128;
+    let count = len / step_i128 + i128::from(len % step_i128 != 0);
+    if count > limit as i128 {
+        return Err(crate::options::limit_error(
+            quote::quote!(#start_tokens #end_tokens),
+            count,
+            limit,
+        ));
+    }
+
+    Ok(Range {
+        values: (start.value..end_value).step_by(step).map(Value::Int).collect(),
+        format: LitFormat::merge(start.format.as_ref(), end.format.as_ref()),
+        tokens: proc_macro2::TokenStream::new(),
+    })
+}
+
+/// `[u8, Vec<u8>, 1 + 2]`
+fn parse_list(input: syn::parse::ParseStream) -> syn::Result<Range> {
+    let content;
+    syn::bracketed!(content in input);
+    let items = content.parse_terminated::<_, Token!(,)>(parse_item)?;
+    Ok(Range {
+        values: items.into_iter().map(Value::Item).collect(),
+        format: LitFormat::default(),
+        tokens: proc_macro2::TokenStream::new(),
+    })
+}
+
+/// 列表项优先按类型解析，否则按表达式解析
+fn parse_item(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let fork = input.fork();
+    if fork.parse::<syn::Type>().is_ok() && (fork.is_empty() || fork.peek(Token!(,))) {
+        let ty: syn::Type = input.parse()?;
+        return Ok(quote::quote!(#ty));
+    }
+    let expr: syn::Expr = input.parse()?;
+    //复合表达式加上括号，代入 `X * 2` 这样的上下文时保持优先级
+    Ok(match expr {
+        syn::Expr::Lit(_) | syn::Expr::Path(_) | syn::Expr::Paren(_) => quote::quote!(#expr),
+        _ => quote::quote!((#expr)),
+    })
+}
+
+/// 表达式一直延续到 `step`、循环体的花括号、分隔多个范围的逗号或输入结尾
+fn collect_until_body(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let mut ret = proc_macro2::TokenStream::new();
+    while !input.is_empty() && !input.peek(syn::token::Brace) && !input.peek(step) && !input.peek(Token!(,)) {
+        ret.extend([input.parse::<proc_macro2::TokenTree>()?]);
+    }
+    Ok(ret)
+}
+
+/// 一个已求值的范围边界
+struct Bound {
+    value: i128,
+    /// 边界字面量的格式
+    format: Option<LitFormat>,
+}
+
+/// 生成字面量时沿用的进制、位数与后缀
+#[derive(Clone)]
+pub(crate) struct LitFormat {
+    radix: Radix,
+    /// 写下的位数，包括前导零
+    width: usize,
+    suffix: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+}
+
+impl Default for LitFormat {
+    fn default() -> Self {
+        LitFormat {
+            radix: Radix::Decimal,
+            width: 1,
+            suffix: String::new(),
+        }
+    }
+}
+
+impl LitFormat {
+    fn from_lit(lit: &syn::LitInt) -> Self {
+        let repr = lit.to_string();
+        let (radix, digits) = match repr.get(..2) {
+            Some("0x") => (Radix::Hex, &repr[2..]),
+            Some("0o") => (Radix::Octal, &repr[2..]),
+            Some("0b") => (Radix::Binary, &repr[2..]),
+            _ => (Radix::Decimal, &repr[..]),
+        };
+        let digits = &digits[..digits.len() - lit.suffix().len()];
+        LitFormat {
+            radix,
+            width: digits.chars().filter(|c| *c != '_').count(),
+            suffix: lit.suffix().to_string(),
+        }
+    }
+
+    /// 合并起止两端的格式：进制与位数取自起点，没有后缀时借用终点的后缀
+    fn merge(start: Option<&LitFormat>, end: Option<&LitFormat>) -> LitFormat {
+        let mut ret = start.or(end).cloned().unwrap_or_default();
+        if ret.suffix.is_empty() {
+            if let Some(end) = end {
+                ret.suffix = end.suffix.clone();
+            }
+        }
+        ret
+    }
+
+    /// `#[suffix = ...]` 给出的后缀优先于范围中写的后缀
+    pub(crate) fn set_suffix(&mut self, suffix: &syn::Ident) {
+        self.suffix = suffix.to_string();
+    }
+
+    /// 后缀对应的整数类型能否表示 `n`，没有后缀时不做检查
+    ///
+    /// `usize` 与 `isize` 按 64 位检查，更窄的目标平台留给 rustc
+    pub(crate) fn check_fits(&self, n: i128, tokens: impl quote::ToTokens) -> syn::Result<()> {
+        let (min, max) = match integer_bounds(&self.suffix) {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        if n < min || n > max {
+            return Err(syn::Error::new_spanned(
+                tokens,
+                format!("{} does not fit in `{}`, which holds {}..={}", n, self.suffix, min, max),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `before_dot` 时负数加上括号，`N.pow(2)` 中的 `-3` 才不会被解析为 `-(3.pow(2))`
+    pub(crate) fn literal(&self, n: i128, span: proc_macro2::Span, before_dot: bool) -> proc_macro2::TokenTree {
+        let width = self.width;
+        let abs = n.unsigned_abs();
+        let digits = match self.radix {
+            Radix::Binary => format!("0b{:0width$b}", abs),
+            Radix::Octal => format!("0o{:0width$o}", abs),
+            Radix::Decimal => format!("{:0width$}", abs),
+            Radix::Hex => format!("0x{:0width$x}", abs),
+        };
+        let sign = if n < 0 { "-" } else { "" };
+        let mut literal = proc_macro2::Literal::from_str(&format!("{}{}{}", sign, digits, self.suffix))
+            .expect("integer literal");
+        literal.set_span(span);
+        if n >= 0 || !before_dot {
+            return proc_macro2::TokenTree::Literal(literal);
+        }
+        let literal = proc_macro2::TokenStream::from(proc_macro2::TokenTree::Literal(literal));
+        let mut group = proc_macro2::Group::new(proc_macro2::Delimiter::Parenthesis, literal);
+        group.set_span(span);
+        proc_macro2::TokenTree::Group(group)
+    }
+}
+
+/// 整数类型的取值范围，`u128` 的上限受 `i128` 所限
+pub(crate) fn integer_bounds(ty: &str) -> Option<(i128, i128)> {
+    Some(match ty {
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" | "isize" => (i64::MIN.into(), i64::MAX.into()),
+        "i128" => (i128::MIN, i128::MAX),
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" | "usize" => (0, u64::MAX.into()),
+        "u128" => (0, i128::MAX),
+        _ => return None,
+    })
+}
+
+fn parse_bound(tokens: proc_macro2::TokenStream) -> syn::Result<Bound> {
+    let expr: syn::Expr = syn::parse2(tokens)?;
+    Ok(Bound {
+        value: eval(&expr)?,
+        format: literal_format(&expr),
+    })
+}
+
+/// 求值常量表达式，支持整数字面量、括号、一元 `-` `!` 与常见二元运算
+pub(crate) fn eval(expr: &syn::Expr) -> syn::Result<i128> {
+    let overflow = || syn::Error::new_spanned(expr, "arithmetic overflow in constant expression");
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => lit.base10_parse(),
+        syn::Expr::Paren(e) => eval(&e.expr),
+        syn::Expr::Group(e) => eval(&e.expr),
+        syn::Expr::Unary(e) => {
+            let value = eval(&e.expr)?;
+            match e.op {
+                syn::UnOp::Neg(_) => value.checked_neg().ok_or_else(overflow),
+                syn::UnOp::Not(_) => Ok(!value),
+                syn::UnOp::Deref(_) => Err(syn::Error::new_spanned(expr, "unsupported operator in constant expression")),
+            }
+        }
+        syn::Expr::Binary(e) => {
+            let lhs = eval(&e.left)?;
+            let rhs = eval(&e.right)?;
+            let shift = || u32::try_from(rhs).ok();
+            let value = match e.op {
+                syn::BinOp::Add(_) => lhs.checked_add(rhs),
+                syn::BinOp::Sub(_) => lhs.checked_sub(rhs),
+                syn::BinOp::Mul(_) => lhs.checked_mul(rhs),
+                syn::BinOp::Div(_) | syn::BinOp::Rem(_) if rhs == 0 => {
+                    return Err(syn::Error::new_spanned(expr, "division by zero in constant expression"));
+                }
+                syn::BinOp::Div(_) => lhs.checked_div(rhs),
+                syn::BinOp::Rem(_) => lhs.checked_rem(rhs),
+                //`checked_shl` 不检查移出的位
+                syn::BinOp::Shl(_) => shift().and_then(|s| lhs.checked_shl(s).filter(|v| v >> s == lhs)),
+                syn::BinOp::Shr(_) => shift().and_then(|s| lhs.checked_shr(s)),
+                syn::BinOp::BitAnd(_) => Some(lhs & rhs),
+                syn::BinOp::BitOr(_) => Some(lhs | rhs),
+                syn::BinOp::BitXor(_) => Some(lhs ^ rhs),
+                _ => return Err(syn::Error::new_spanned(e.op, "unsupported operator in constant expression")),
+            };
+            value.ok_or_else(overflow)
+        }
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "expected an integer literal or a constant expression of integer literals",
+        )),
+    }
+}
+
+/// 求值 `#if` 的条件
+pub(crate) fn eval_condition(expr: &syn::Expr) -> syn::Result<bool> {
+    match expr {
+        syn::Expr::Paren(e) => eval_condition(&e.expr),
+        syn::Expr::Group(e) => eval_condition(&e.expr),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Not(_), expr, .. }) => Ok(!eval_condition(expr)?),
+        syn::Expr::Binary(e) => match e.op {
+            syn::BinOp::And(_) => Ok(eval_condition(&e.left)? && eval_condition(&e.right)?),
+            syn::BinOp::Or(_) => Ok(eval_condition(&e.left)? || eval_condition(&e.right)?),
+            syn::BinOp::Eq(_) => Ok(eval(&e.left)? == eval(&e.right)?),
+            syn::BinOp::Ne(_) => Ok(eval(&e.left)? != eval(&e.right)?),
+            syn::BinOp::Lt(_) => Ok(eval(&e.left)? < eval(&e.right)?),
+            syn::BinOp::Le(_) => Ok(eval(&e.left)? <= eval(&e.right)?),
+            syn::BinOp::Gt(_) => Ok(eval(&e.left)? > eval(&e.right)?),
+            syn::BinOp::Ge(_) => Ok(eval(&e.left)? >= eval(&e.right)?),
+            _ => Err(syn::Error::new_spanned(expr, "expected a comparison in the condition of `#if`")),
+        },
+        _ => Err(syn::Error::new_spanned(expr, "expected a comparison in the condition of `#if`")),
+    }
+}
+
+/// 边界直接写成（可能带负号的）字面量时，取该字面量的格式
+fn literal_format(expr: &syn::Expr) -> Option<LitFormat> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => Some(LitFormat::from_lit(lit)),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => literal_format(expr),
+        syn::Expr::Paren(e) => literal_format(&e.expr),
+        syn::Expr::Group(e) => literal_format(&e.expr),
+        _ => None,
+    }
+}
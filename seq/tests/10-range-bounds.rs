@@ -0,0 +1,71 @@
+// The range bounds do not have to be plain decimal literals. Each bound may be
+// negative, written in hex, octal or binary, carry a type suffix, or be a
+// constant expression built from integer literals and the usual arithmetic
+// and bitwise operators, evaluated when the macro expands.
+//
+// The values substituted for N keep the radix, digit count and suffix the
+// range was written with, so in the second invocation below N expands to
+// `0x00u8`, `0x01u8`, ..., `0x0fu8`. Negative values are substituted as
+// plain literals such as `-2`, so they work as const generic arguments, and
+// are only parenthesized before a `.`, so that calling a method on N applies
+// it to the whole value.
+
+use seq::seq;
+
+fn negative() -> Vec<i32> {
+    let mut ret = Vec::new();
+    seq!(N in -3..3 {
+        ret.push(N);
+    });
+    ret
+}
+
+const MASKS: [u8; 16] = seq!(N in 0x00u8..0x10u8 {
+    [#(N,)*]
+});
+
+seq!(N in 0..(1 << 2) {
+    #[derive(Debug, PartialEq)]
+    enum Lane {
+        #(
+            Lane~N,
+        )*
+    }
+});
+
+struct Const<const X: i32>;
+
+impl<const X: i32> Const<X> {
+    const VALUE: i32 = X;
+}
+
+fn main() {
+    assert_eq!(negative(), [-3, -2, -1, 0, 1, 2]);
+
+    let squares = seq!(N in -3i32..-1i32 { [#(N.pow(2),)*] });
+    assert_eq!(squares, [9, 4]);
+    let generic = seq!(N in -2..0 { [#(Const::<N>::VALUE,)*] });
+    assert_eq!(generic, [-2, -1]);
+
+    let abs = seq!(N in -2i64..=-1 { [#(#(N * 3).abs(),)*] });
+    assert_eq!(abs, [6, 3]);
+
+    assert_eq!(MASKS[15], 15u8);
+    let hex = seq!(N in 0x0eu8..0x10u8 { [#(stringify!(N),)*] });
+    assert_eq!(hex, ["0x0eu8", "0x0fu8"]);
+
+    let bits = seq!(N in 0b0001..=0b0011 { [#(stringify!(N),)*] });
+    assert_eq!(bits, ["0b0001", "0b0010", "0b0011"]);
+
+    let octal = seq!(N in 0o7..0o11 { [#(stringify!(N),)*] });
+    assert_eq!(octal, ["0o7", "0o10"]);
+
+    let suffixed = seq!(N in 0..=2usize { [#(stringify!(N),)*] });
+    assert_eq!(suffixed, ["0usize", "1usize", "2usize"]);
+
+    let lanes = seq!(N in 0..(1 << 2) { [#(Lane::Lane~N,)*] });
+    assert_eq!(lanes, [Lane::Lane0, Lane::Lane1, Lane::Lane2, Lane::Lane3]);
+
+    let computed = seq!(N in (2 * 3 - 8)..-(-1) { [#(N,)*] });
+    assert_eq!(computed, [-2, -1, 0]);
+}
@@ -0,0 +1,47 @@
+// Register tables often need every 4th index, or need to be laid out in
+// descending order. The range may be followed by `step K`, which keeps every
+// K-th value like `Iterator::step_by`, and may be wrapped as `(range).rev()`
+// to iterate it backwards. Both apply in the order they are written, so
+// `(0..8 step 3).rev()` is 6, 3, 0 while `(0..8).rev() step 3` is 7, 4, 1.
+//
+// The resulting sequence is used both when the whole body is repeated and
+// when only `#(...)*` sections are.
+
+use seq::seq;
+
+seq!(N in 0..16 step 4 {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum Offset {
+        #(
+            Reg~N = N,
+        )*
+    }
+});
+
+fn descending() -> Vec<u32> {
+    let mut ret = Vec::new();
+    seq!(N in (0..4).rev() {
+        ret.push(N);
+    });
+    ret
+}
+
+fn main() {
+    let offsets = seq!(N in 0..=16 step 4 { [#(N,)*] });
+    assert_eq!(offsets, [0, 4, 8, 12, 16]);
+    assert_eq!(Offset::Reg12 as u32, 12);
+
+    assert_eq!(descending(), [3, 2, 1, 0]);
+
+    let stepped_then_reversed = seq!(N in (0..8 step 3).rev() { [#(N,)*] });
+    assert_eq!(stepped_then_reversed, [6, 3, 0]);
+
+    let reversed_then_stepped = seq!(N in (0..8).rev() step 3 { [#(N,)*] });
+    assert_eq!(reversed_then_stepped, [7, 4, 1]);
+
+    let registers = seq!(N in (0..16 step 4).rev() { [#(Offset::Reg~N,)*] });
+    assert_eq!(registers, [Offset::Reg12, Offset::Reg8, Offset::Reg4, Offset::Reg0]);
+
+    let expr_step = seq!(N in 0..(1 << 4) step (1 << 3) { [#(N,)*] });
+    assert_eq!(expr_step, [0, 8]);
+}
@@ -0,0 +1,54 @@
+// Matrix code wants two loop variables. There are two ways to get them.
+//
+// A `seq!` may be nested in the body of another, as long as the two use
+// distinct variables. The outer variable is substituted everywhere in the
+// inner invocation, including its range, while `#(...)*` sections inside the
+// inner invocation belong to the inner one.
+//
+// Alternatively a single header may declare several variables ranging over
+// the cartesian product of their ranges, the first variable varying slowest:
+//
+//     seq!((R, C) in (0..2, 0..3) { ... })
+//
+// expands the body for (0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2).
+//
+// Identifiers may paste several variables in a row, as in `Cell~R~C`.
+
+use seq::seq;
+
+seq!((R, C) in (0..2, 0..3) {
+    #[derive(Debug, PartialEq)]
+    enum Cell {
+        #(
+            Cell~R~C,
+        )*
+    }
+});
+
+fn triangle() -> Vec<(u32, u32)> {
+    let mut ret = Vec::new();
+    seq!(N in 0..3 {
+        seq!(M in 0..=N {
+            #(
+                ret.push((N, M));
+            )*
+        });
+    });
+    ret
+}
+
+fn main() {
+    let cells = seq!((R, C) in (0..2, 0..3) { [#(Cell::Cell~R~C,)*] });
+    assert_eq!(cells[4], Cell::Cell11);
+    assert_eq!(cells.len(), 6);
+
+    let products = seq!((R, C) in (1..3, (0..2).rev()) { [#(R * 10 + C,)*] });
+    assert_eq!(products, [11, 10, 21, 20]);
+
+    assert_eq!(triangle(), [(0, 0), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)]);
+
+    let names = seq!(R in 0..2 {
+        [#(seq!(C in 0..2 { [#(stringify!(Cell~R~C),)*] }),)*]
+    });
+    assert_eq!(names, [["Cell00", "Cell01"], ["Cell10", "Cell11"]]);
+}
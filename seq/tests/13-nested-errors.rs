@@ -0,0 +1,13 @@
+use seq::seq;
+
+seq!(N in 0..2 {
+    seq!(N in 0..2 {
+        fn f~N() {}
+    });
+});
+
+seq!((R, R) in (0..2, 0..2) {});
+
+seq!((R, C) in (0..2) {});
+
+fn main() {}
@@ -0,0 +1,53 @@
+// Generated code often needs values derived from the loop variable, such as
+// bit offsets `N * 8` or a neighbouring index `N + 1`. Inside the body,
+// `#(expr)` and `~(expr)` evaluate a constant expression mentioning the loop
+// variable and expand to a single literal, in the same format as the
+// variable's own literals. The `~(expr)` form can also be pasted onto an
+// identifier, as in `Field~(N + 1)`.
+//
+// A `#(...)` is only a repeat section when the `*`, or the separator before
+// it, is written right against the closing parenthesis, so `#(N * 8) * 2`
+// is an evaluated offset multiplied by two.
+
+use seq::seq;
+
+seq!(N in 0..4 {
+    #[derive(Debug, PartialEq)]
+    enum Field {
+        #(
+            Field~(N + 1) = #(N * 8),
+        )*
+    }
+});
+
+fn shifts() -> Vec<u32> {
+    let mut ret = Vec::new();
+    seq!(N in 0..3 {
+        ret.push(1u32 << ~(N * 4));
+    });
+    ret
+}
+
+fn main() {
+    assert_eq!(Field::Field1 as u8, 0);
+    assert_eq!(Field::Field4 as u8, 24);
+
+    assert_eq!(shifts(), [1, 1 << 4, 1 << 8]);
+
+    let mut doubled = Vec::new();
+    seq!(N in 0..3 {
+        doubled.push(#(N * 8) * 2);
+    });
+    assert_eq!(doubled, [0, 16, 32]);
+
+    let offsets = seq!(N in 0x00u8..0x03u8 { [#(stringify!(#(N * 0x10)),)*] });
+    assert_eq!(offsets, ["0x00u8", "0x10u8", "0x20u8"]);
+
+    let previous = seq!(N in 1..=3 { [#(#(N - 1),)*] });
+    assert_eq!(previous, [0, 1, 2]);
+
+    let pairs = seq!(N in 0..2 {
+        [#(seq!(M in 0..2 { [#(#(N * 2 + M),)*] }),)*]
+    });
+    assert_eq!(pairs, [[0, 1], [2, 3]]);
+}
@@ -0,0 +1,76 @@
+// Beyond `prefix~N`, identifiers can be pasted from any run of segments
+// joined by `~`, each segment being an identifier, the loop variable, or a
+// parenthesized expression of it:
+//
+//     f~N~_handler       f0_handler, f1_handler, ...
+//     Slot~(N + 1)~Ptr   Slot1Ptr, Slot2Ptr, ...
+//
+// Segments take modifiers written directly after them. `:02` zero-pads the
+// loop variable to the given width, while `:upper`, `:lower`, `:snake`,
+// `:pascal` and `:camel` convert the case of a segment:
+//
+//     Irq~N:02           Irq00, Irq01, ...
+//     reg:upper~_~N:02   REG_00, REG_01, ...
+//     my_type:pascal~N   MyType0, MyType1, ...
+//     read_reg:camel~N   readReg0, readReg1, ...
+
+use seq::seq;
+
+seq!(N in 0..32 {
+    #(
+        #[allow(dead_code)]
+        const reg:upper~_~N:02: u32 = N * 4;
+    )*
+});
+
+seq!(N in 0..3 {
+    #(
+        fn f~N~_handler() -> u32 {
+            N
+        }
+    )*
+});
+
+seq!(N in 0..12 {
+    #[derive(Debug, PartialEq)]
+    enum Irq {
+        #(
+            Irq~N:02,
+        )*
+    }
+
+    #(
+        #[allow(dead_code)]
+        struct my_type:pascal~N;
+    )*
+});
+
+fn main() {
+    assert_eq!(REG_00, 0);
+    assert_eq!(REG_31, 124);
+
+    assert_eq!(f0_handler() + f1_handler() + f2_handler(), 3);
+
+    assert_eq!(Irq::Irq07 as u8, 7);
+    assert_eq!(format!("{:?}", Irq::Irq11), "Irq11");
+    let _ = MyType11;
+
+    let names = seq!(N in 0..2 { [#(stringify!(Slot~(N + 1)~Ptr),)*] });
+    assert_eq!(names, ["Slot1Ptr", "Slot2Ptr"]);
+
+    let snake = seq!(N in 8..9 { stringify!(HttpClient:snake~_~N:03) });
+    assert_eq!(snake, "http_client_008");
+
+    let camel = seq!(N in 0..2 { [#(stringify!(read_reg:camel~N),)*] });
+    assert_eq!(camel, ["readReg0", "readReg1"]);
+    let camel = seq!(N in 0..1 { stringify!(HttpClient:camel~N) });
+    assert_eq!(camel, "httpClient0");
+
+    // not a paste chain: the field type is left alone
+    seq!(N in 0..1 {
+        struct Field { value~N: u8 }
+        let _ = Field { value~N:0 };
+        let field = Field { value0: 5 };
+        assert_eq!(field.value0, 5);
+    });
+}
@@ -0,0 +1,15 @@
+use seq::seq;
+
+seq!(N in -1..0 {
+    fn f~N() {}
+});
+
+seq!(N in 0..1 {
+    fn N~th() {}
+});
+
+seq!(N in 0..1 {
+    fn f:02~N() {}
+});
+
+fn main() {}
@@ -0,0 +1,65 @@
+// Not every repetition is numeric. In place of a range, the values may be
+// given as a list of types, identifiers or literals, each of which is
+// substituted for the variable as written:
+//
+//     seq!(T in [u8, u16, u32, u64] {
+//         impl Width for T { ... }
+//     });
+//
+// A list entry that is a single identifier can also be pasted, so `read_~T`
+// becomes `read_u8`, `read_u16` and so on.
+//
+// With two variables and `enumerate`, the first one counts up from zero
+// alongside the entries of the list:
+//
+//     seq!((I, T) in enumerate [u8, u16] { ... });
+
+use seq::seq;
+
+trait Width {
+    const BITS: u32;
+}
+
+seq!(T in [u8, u16, u32, u64, Vec<u8>] {
+    impl Width for T {
+        const BITS: u32 = std::mem::size_of::<T>() as u32 * 8;
+    }
+});
+
+seq!((I, T) in enumerate [u8, u16, u32] {
+    #[derive(Debug, PartialEq)]
+    #[allow(non_camel_case_types)]
+    enum Kind {
+        #(
+            T~_~I,
+        )*
+    }
+
+    #(
+        fn read_~T(bytes: &[u8]) -> T {
+            let mut buf = [0; std::mem::size_of::<T>()];
+            buf.copy_from_slice(&bytes[..std::mem::size_of::<T>()]);
+            T::from_le_bytes(buf)
+        }
+    )*
+});
+
+fn main() {
+    assert_eq!(<u16 as Width>::BITS, 16);
+    assert_eq!(<Vec<u8> as Width>::BITS, 8 * std::mem::size_of::<Vec<u8>>() as u32);
+
+    assert_eq!(Kind::u32_2 as u8, 2);
+    assert_eq!(read_u16(&[1, 2]), 0x0201);
+
+    let words = seq!(W in ["zero", "one", "two"] { [#(W,)*] });
+    assert_eq!(words, ["zero", "one", "two"]);
+
+    let doubled = seq!(X in [1 + 1, 3] { [#(X * 2,)*] });
+    assert_eq!(doubled, [4, 6]);
+
+    let reversed = seq!((I, W) in enumerate ([10, 20, 30]).rev() { [#((I, W),)*] });
+    assert_eq!(reversed, [(0, 30), (1, 20), (2, 10)]);
+
+    let pairs = seq!((R, T) in (0..2, [i8, i16]) { [#(std::mem::size_of::<T>() * 10 + R,)*] });
+    assert_eq!(pairs, [10, 20, 11, 21]);
+}
@@ -0,0 +1,47 @@
+// A repeat section may put a separator between its repetitions, the same as
+// in macro_rules: `#(...),*` joins them with commas and `#(...);*` with
+// semicolons, without a trailing one after the last repetition. Any single
+// punctuation character other than `*` works.
+//
+// A body may contain any number of sections, at any depth of nesting, and
+// everything outside of them is emitted once. Whether the body contains a
+// section is decided over all of it, so a group without one appearing after
+// a group with one does not cause the whole body to be repeated instead.
+
+use seq::seq;
+
+seq!(N in 0..3 {
+    fn sum(#(x~N: u32),*) -> u32 {
+        0 #(+ x~N)*
+    }
+
+    fn product(#(x~N: u32),*) -> u32 {
+        1 #(* x~N)*
+    }
+
+    fn tuple() -> (#(u8~N),*) where (#(u8~N),*): Copy {
+        (#(N),*)
+    }
+
+    fn last() -> u32 {
+        #(let _ = N);*;
+        [7][0]
+    }
+});
+
+#[allow(non_camel_case_types)]
+type u80 = u8;
+#[allow(non_camel_case_types)]
+type u81 = u8;
+#[allow(non_camel_case_types)]
+type u82 = u8;
+
+fn main() {
+    assert_eq!(sum(1, 2, 3), 6);
+    assert_eq!(product(2, 3, 4), 24);
+    assert_eq!(tuple(), (0, 1, 2));
+    assert_eq!(last(), 7);
+
+    let bits = seq!(N in 0..4 { 0 #(| 1 << N)* });
+    assert_eq!(bits, 0b1111);
+}
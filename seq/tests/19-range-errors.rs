@@ -0,0 +1,27 @@
+use seq::seq;
+
+seq!(N in 8..4 {});
+
+seq!(N in 8..=4 {});
+
+seq!(N in 4..4 {});
+
+seq!(T in [] {});
+
+seq!(N in 0..100000 {});
+
+seq!((R, C) in (0..200, 0..200) {});
+
+seq!(#[limit = 8] N in 0..16 {});
+
+seq!(#[allow_empty] #[allow_empty] N in 0..0 {});
+
+seq!(#[expand_all] N in 0..1 {});
+
+seq!(N in 0..=170141183460469231731687303715884105727 {});
+
+seq!(N in -170141183460469231731687303715884105727..170141183460469231731687303715884105727 {});
+
+seq!(N in (3 << 126)..((3 << 126) + 2) {});
+
+fn main() {}
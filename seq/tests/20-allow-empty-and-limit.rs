@@ -0,0 +1,33 @@
+// Ranges that are empty are usually a mistake, so seq! reports them, as it
+// does ranges that start above their end. When an empty expansion is
+// intended, for example because the bound comes from an enclosing seq!, say
+// so with `#[allow_empty]` at the start of the invocation.
+//
+// The body is repeated at most 16384 times unless a different limit is given
+// with `#[limit = N]`. The limit counts the values left after `step`, so a
+// long range with a large step is fine.
+
+use seq::seq;
+
+fn counts() -> Vec<usize> {
+    let mut ret = Vec::new();
+    seq!(N in 0..3 {
+        let items: [usize; N] = seq!(#[allow_empty] M in 0..N { [#(M,)*] });
+        ret.push(items.len());
+    });
+    ret
+}
+
+const TABLE: [u32; 20000] = seq!(#[limit = 20000] N in 0..20000 { [#(N,)*] });
+
+fn main() {
+    assert_eq!(counts(), [0, 1, 2]);
+    assert_eq!(TABLE[19999], 19999);
+
+    let sparse = seq!(N in 0..100000 step 10000 { [#(N,)*] });
+    assert_eq!(sparse.len(), 10);
+    assert_eq!(sparse[9], 90000);
+
+    let nothing: [u8; 0] = seq!(#[allow_empty] T in [] { [#(T,)*] });
+    assert!(nothing.is_empty());
+}
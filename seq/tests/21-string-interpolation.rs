@@ -0,0 +1,39 @@
+// Literal tokens are normally left alone, so by default neither strings nor
+// doc comments can mention the loop variable. Inside a string literal, `~N`
+// interpolates the variable, and `~{N}` does the same when it is directly
+// followed by letters or digits. Doc comments are string literals too once
+// the compiler turns them into `#[doc = "..."]`, so `/// Register ~N` works.
+//
+// A `~` that is not followed by a loop variable is kept as written.
+
+use seq::seq;
+
+seq!(N in 0..4 {
+    #(
+        /// Handler for interrupt ~N, see `IRQ_NAMES[~N]`.
+        fn handler~N() -> &'static str {
+            "irq ~N"
+        }
+    )*
+
+    const IRQ_NAMES: [&str; 4] = [#("IRQ~{N}h",)*];
+});
+
+fn main() {
+    assert_eq!(handler2(), "irq 2");
+    assert_eq!(IRQ_NAMES, ["IRQ0h", "IRQ1h", "IRQ2h", "IRQ3h"]);
+
+    let kept = seq!(N in 0..1 { "~M and ~ and ~{M}" });
+    assert_eq!(kept, "~M and ~ and ~{M}");
+
+    let raw = seq!(N in 5..6 { r"C:\reg~N" });
+    assert_eq!(raw, "C:\\reg5");
+
+    let names = seq!(T in [u8, i64] { [#(concat!("read_~T", "~T"),)*] });
+    assert_eq!(names, ["read_u8u8", "read_i64i64"]);
+
+    let nested = seq!(R in 0..2 {
+        [#(seq!(C in 0..2 { [#("cell ~R,~C",)*] }),)*]
+    });
+    assert_eq!(nested, [["cell 0,0", "cell 0,1"], ["cell 1,0", "cell 1,1"]]);
+}
@@ -0,0 +1,54 @@
+// Wrapping whole items in `seq! { ... }` adds a level of indentation and
+// hides them from rustfmt. The attribute `#[seq::repeat(...)]` takes the same
+// header as `seq!` and repeats the item it is placed on instead.
+//
+// The item has to be valid Rust before the attribute runs, so it cannot use
+// `~` to paste identifiers. In the attribute form, every `__`-separated part
+// of an identifier that names a loop variable is replaced, dropping the `__`:
+// `handler__N` becomes `handler0`, `handler1`, ...
+//
+// On an enum, the variants marked `#[each]` are repeated in place and the
+// rest of the enum is emitted once.
+
+#[seq::repeat(N in 0..4)]
+fn handler__N() -> u32 {
+    N * 10
+}
+
+#[seq::repeat(T in [u8, u16])]
+impl Describe for T {
+    fn describe() -> &'static str {
+        "integer ~T"
+    }
+}
+
+trait Describe {
+    fn describe() -> &'static str;
+}
+
+#[seq::repeat(N in 0..4)]
+#[derive(Debug, PartialEq)]
+enum Irq {
+    Reserved,
+    /// Interrupt line ~N
+    #[each]
+    Line__N = N + 1,
+    Spurious = 0xff,
+}
+
+#[seq::repeat((I, T) in enumerate [u32, u64])]
+#[allow(non_upper_case_globals)]
+const WIDTH__T: usize = std::mem::size_of::<T>() + I - I;
+
+fn main() {
+    assert_eq!(handler0() + handler3(), 30);
+
+    assert_eq!(<u16 as Describe>::describe(), "integer u16");
+
+    assert_eq!(Irq::Reserved as u8, 0);
+    assert_eq!(Irq::Line0 as u8, 1);
+    assert_eq!(Irq::Line3 as u8, 4);
+    assert_eq!(Irq::Spurious as u8, 0xff);
+
+    assert_eq!(WIDTHu32 + WIDTHu64, 12);
+}
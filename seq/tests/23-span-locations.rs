@@ -0,0 +1,37 @@
+// Tokens generated by seq! keep the location of what the caller wrote, so
+// errors inside the body point at the substituted variable or the pasted
+// identifier rather than at the whole invocation, including inside groups and
+// repeat sections.
+//
+// Underlining the whole of `Missing~N` needs Span::join, which only works on
+// a nightly compiler. On stable, which this snapshot is recorded with, a
+// pasted identifier has the location of its first segment, so only `Missing`
+// is underlined below.
+//
+// `#[hygiene = "mixed_site"]` additionally hides pasted local variables from
+// code outside the generated body, the way locals introduced by macro_rules!
+// are hidden.
+
+use seq::seq;
+
+seq!(N in 0..1 {
+    fn text() -> &'static str {
+        N
+    }
+});
+
+seq!(N in 0..2 {
+    fn sections() {
+        #(
+            let _ = [Missing~N];
+        )*
+    }
+});
+
+fn main() {
+    seq!(#[hygiene = "mixed_site"] N in 0..1 {
+        let value~N = N;
+        let _ = value~N;
+    });
+    let _ = value0;
+}
@@ -0,0 +1,62 @@
+// Parts of the body can be kept or dropped per iteration. `#if` takes a
+// constant condition of the loop variables, optionally followed by `#else`
+// or a chain of `#else #if`, and `#first` and `#last` keep their block only
+// in the first and the last iteration. The braces around the kept block are
+// not emitted, so it may hold items as well as statements.
+
+use seq::seq;
+
+seq!(N in 0..6 {
+    #if N % 2 == 0 {
+        const EVEN~N: bool = true;
+    } #else {
+        const ODD~N: bool = true;
+    }
+});
+
+seq!(N in 0..5 {
+    fn kind~N() -> &'static str {
+        #if N == 0 { "zero" } #else #if N < 3 && !(N == 1) { "small" } #else #if N == 1 { "one" } #else { "large" }
+    }
+});
+
+fn join() -> String {
+    let mut s = String::new();
+    seq!(N in 1..=3 {
+        #(
+            #first { s.push('['); }
+            s.push_str(&N.to_string());
+            #last { s.push(']'); } #if N != 3 { s.push(','); }
+        )*
+    });
+    s
+}
+
+seq!(R in 0..2 {
+    fn row~R() -> Vec<u32> {
+        let mut v = Vec::new();
+        seq!(C in 0..3 {
+            #if R == 1 { v.push(100); }
+            #(
+                #if C > 0 || R > 0 { v.push(C); }
+                #last { v.push(9); }
+            )*
+        });
+        v
+    }
+});
+
+fn main() {
+    assert!(EVEN0 && EVEN2 && EVEN4);
+    assert!(ODD1 && ODD3 && ODD5);
+
+    assert_eq!(kind0(), "zero");
+    assert_eq!(kind1(), "one");
+    assert_eq!(kind2(), "small");
+    assert_eq!(kind4(), "large");
+
+    assert_eq!(join(), "[1,2,3]");
+
+    assert_eq!(row0(), vec![1, 2, 9]);
+    assert_eq!(row1(), vec![100, 0, 1, 2, 9]);
+}
@@ -0,0 +1,34 @@
+// `#[suffix = T]` gives every literal substituted for a numeric variable the
+// suffix of the integer type T, which helps inference in code where the type
+// of the loop variable would otherwise fall back to i32. A suffix written on
+// the bounds is carried through the same way, and the option takes
+// precedence over it.
+
+use seq::seq;
+
+fn size_of_val<T>(_: T) -> usize {
+    std::mem::size_of::<T>()
+}
+
+fn main() {
+    seq!(#[suffix = usize] N in 0..4 {
+        let sizes = [#(size_of_val(N),)*];
+    });
+    assert_eq!(sizes, [std::mem::size_of::<usize>(); 4]);
+
+    seq!(N in 0u8..4 {
+        let bytes = [#(size_of_val(#(N * 60)),)*];
+    });
+    assert_eq!(bytes, [1; 4]);
+
+    seq!(#[suffix = i64] N in 0u8..2 {
+        let wide = [#(size_of_val(N),)*];
+    });
+    assert_eq!(wide, [8; 2]);
+
+    seq!(#[suffix = u16] (I, T) in enumerate [u8, u16] {
+        #(let _: T = 0;)*
+        let indices = [#(size_of_val(I),)*];
+    });
+    assert_eq!(indices, [2; 2]);
+}
@@ -0,0 +1,15 @@
+use seq::seq;
+
+seq!(N in 250u8..=256 {});
+
+seq!(#[suffix = u8] N in -1..1 {});
+
+seq!(N in 0i8..4 {
+    const X~N: i8 = #(N * 50);
+});
+
+seq!(#[suffix = f32] N in 0..1 {});
+
+seq!(#[suffix = usize] #[suffix = usize] N in 0..1 {});
+
+fn main() {}
@@ -0,0 +1,41 @@
+// A proc macro cannot read the value of a const defined elsewhere, so a bound
+// shared between several seq! invocations has to come from a macro instead,
+// as in test 09. `seq::with_const!` makes that macro a one-liner: it replaces
+// each name it is given by its value in the tokens that follow, so seq! sees
+// a literal bound, and code outside of seq! can use the same name.
+//
+// The tokens can come from a macro_rules! wrapper holding the single source
+// of truth, and may pass through further macro_rules! indirection before
+// reaching seq!.
+
+use seq::seq;
+
+macro_rules! with_channels {
+    ($($body:tt)*) => {
+        seq::with_const! { NUM_CHANNELS = 4, FIRST = 2 * 1; $($body)* }
+    };
+}
+
+macro_rules! channel_fns {
+    ($n:tt) => {
+        seq!(N in 0..$n {
+            fn channel~N() -> usize { N }
+        });
+    };
+}
+
+with_channels! {
+    const CHANNELS: usize = NUM_CHANNELS;
+
+    channel_fns!(NUM_CHANNELS);
+
+    static SKIPPED: [usize; NUM_CHANNELS - FIRST] = seq!(N in FIRST..NUM_CHANNELS {
+        [#(N,)*]
+    });
+}
+
+fn main() {
+    assert_eq!(CHANNELS, 4);
+    assert_eq!(channel0() + channel3(), 3);
+    assert_eq!(SKIPPED, [2, 3]);
+}
@@ -0,0 +1,45 @@
+// `#[debug]` writes the expansion of an invocation, pretty-printed, to
+// `seq-expand/<file>-<line>-<column>.rs` under the target directory and
+// points at it with a deprecation warning, allowed here. Items, statements
+// and expressions can all be written, and the invocation still expands to
+// the same code.
+
+#![allow(deprecated)]
+
+use seq::seq;
+
+seq!(#[debug] N in 0..2 {
+    fn f~N() -> u32 { N }
+});
+
+// File names carry the line and column of each invocation, so look the
+// expansions up by content instead.
+fn expansions() -> Vec<String> {
+    let dir = match option_env!("OUT_DIR").or(option_env!("CARGO_TARGET_DIR")) {
+        Some(dir) => std::path::PathBuf::from(dir).join("seq-expand"),
+        None => panic!("trybuild builds tests with CARGO_TARGET_DIR set"),
+    };
+    let entries = std::fs::read_dir(&dir).unwrap_or_else(|e| panic!("{}: {}", dir.display(), e));
+    entries
+        .map(|entry| entry.expect("directory entry").path())
+        .filter(|path| path.file_name().unwrap().to_string_lossy().starts_with("28-debug-output-"))
+        .map(|path| std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e)))
+        .collect()
+}
+
+fn main() {
+    seq!(#[debug] N in 0..2 {
+        let a~N = f~N();
+    });
+    let sum = seq!(#[debug] N in 0..2 { 0 #(+ a~N)* });
+    assert_eq!(sum, 1);
+
+    let written = expansions();
+    for expected in [
+        "fn f0() -> u32 {\n    0\n}\nfn f1() -> u32 {\n    1\n}\n",
+        "fn seq_expansion() {\n    let a0 = f0();\n    let a1 = f1();\n}\n",
+        "fn seq_expansion() {\n    0 + a0 + a1\n}\n",
+    ] {
+        assert!(written.iter().any(|w| w == expected), "no expansion written as:\n{}", expected);
+    }
+}
@@ -3,11 +3,30 @@ fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/01-parse-header.rs");
     t.pass("tests/02-parse-body.rs");
-    // t.compile_fail("tests/03-expand-four-errors.rs");
+    t.compile_fail("tests/03-expand-four-errors.rs");
     t.pass("tests/04-paste-ident.rs");
     t.pass("tests/05-repeat-section.rs");
-    //t.pass("tests/06-init-array.rs");
+    t.pass("tests/06-init-array.rs");
     t.pass("tests/07-inclusive-range.rs");
     t.compile_fail("tests/08-ident-span.rs");
     t.pass("tests/09-interaction-with-macrorules.rs");
+    t.pass("tests/10-range-bounds.rs");
+    t.pass("tests/11-step-and-rev.rs");
+    t.pass("tests/12-nested-and-product.rs");
+    t.compile_fail("tests/13-nested-errors.rs");
+    t.pass("tests/14-body-arithmetic.rs");
+    t.pass("tests/15-paste-forms.rs");
+    t.compile_fail("tests/16-paste-errors.rs");
+    t.pass("tests/17-lists.rs");
+    t.pass("tests/18-separated-sections.rs");
+    t.compile_fail("tests/19-range-errors.rs");
+    t.pass("tests/20-allow-empty-and-limit.rs");
+    t.pass("tests/21-string-interpolation.rs");
+    t.pass("tests/22-repeat-attribute.rs");
+    t.compile_fail("tests/23-span-locations.rs");
+    t.pass("tests/24-conditionals.rs");
+    t.pass("tests/25-typed-literals.rs");
+    t.compile_fail("tests/26-typed-literal-errors.rs");
+    t.pass("tests/27-with-const.rs");
+    t.pass("tests/28-debug-output.rs");
 }
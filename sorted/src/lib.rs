@@ -23,7 +23,7 @@ fn do_expand(st: &syn::Item)-> syn::Result<proc_macro2::TokenStream>{
         syn::Item::Enum(enum_node)=> check_enum_order(enum_node),
         _ => syn::Result::Err(syn::Error::new(proc_macro2::Span::call_site(), "expected enum or match expression")),
     }?;
-    return Ok(ret);
+    Ok(ret)
 }
 
 fn check_enum_order(st: &syn::ItemEnum) -> syn::Result<proc_macro2::TokenStream>{
@@ -37,7 +37,7 @@ fn check_enum_order(st: &syn::ItemEnum) -> syn::Result<proc_macro2::TokenStream>
             return syn::Result::Err(syn::Error::new(b.1.ident.span(), format!("{} should sort before {}", b.0, a.0)));
         }
     }
-    return syn::Result::Ok(st.to_token_stream());
+    syn::Result::Ok(st.to_token_stream())
 
 }
 #[proc_macro_attribute]
@@ -62,10 +62,9 @@ fn do_match_expand(st:&mut syn::ItemFn) -> syn::Result<proc_macro2::TokenStream>
 
     visitor.visit_item_fn_mut(st);
 
-    if visitor.err.is_none(){
-        return syn::Result::Ok(st.to_token_stream())
-    }else{
-        return syn::Result::Err(visitor.err.unwrap())
+    match visitor.err{
+        None => syn::Result::Ok(st.to_token_stream()),
+        Some(e) => syn::Result::Err(e),
     }
 
 }
@@ -76,7 +75,7 @@ fn get_path_string(p: &syn::Path) -> String{
     for i in &p.segments{
         buf.push(i.ident.to_string());
     }
-    return buf.join("::")
+    buf.join("::")
 }
 
 struct MatchVisitor{
@@ -128,7 +127,7 @@ impl syn::visit_mut::VisitMut for MatchVisitor{
             let mut sorted_names = match_arm_names.clone();
             sorted_names.sort_by(|a,b|{a.0.cmp(&b.0)});
             for (idx,(a,b)) in match_arm_names.iter().zip(sorted_names.iter()).enumerate() {
-                if a.0 == "_".to_string() && idx != match_arm_names.len()-1{
+                if a.0 == "_" && idx != match_arm_names.len()-1{
                     self.err = Some(syn::Error::new_spanned(a.1, "\"_\" should be last one"));
                     return;
                 }